@@ -0,0 +1,83 @@
+use advent_2024::days::{self, SOLUTIONS};
+use advent_2024::{Day, Part};
+use std::{env, error::Error, fs, process};
+
+const USAGE: &str = "usage: aoc run <day|all> [--part 1|2] [--input PATH]";
+
+struct Args {
+    days: Vec<&'static Day>,
+    part: Option<Part>,
+    input: Option<String>,
+}
+
+fn parse_args(mut args: impl Iterator<Item = String>) -> Result<Args, Box<dyn Error>> {
+    if args.next().as_deref() != Some("run") {
+        return Err(USAGE.into());
+    }
+
+    let days = match args.next().as_deref() {
+        Some("all") => SOLUTIONS.iter().collect(),
+        Some(day) => match day.parse().ok().and_then(days::get) {
+            Some(solution) => vec![solution],
+            None => return Err(format!("Invalid day: {day}").into()),
+        },
+        None => return Err(USAGE.into()),
+    };
+
+    let mut part = None;
+    let mut input = None;
+    while let Some(flag) = args.next() {
+        match flag.as_str() {
+            "--part" => match args.next().as_deref() {
+                Some("1") => part = Some(Part::One),
+                Some("2") => part = Some(Part::Two),
+                _ => return Err("--part must be 1 or 2".into()),
+            },
+            "--input" => input = Some(args.next().ok_or("--input requires a path")?),
+            _ => return Err(format!("Unknown argument: {flag}\n{USAGE}").into()),
+        }
+    }
+
+    if input.is_some() && days.len() > 1 {
+        return Err("--input can only be used when running a single day".into());
+    }
+
+    Ok(Args { days, part, input })
+}
+
+fn run_day(solution: &Day, part: Option<Part>, path: &str) -> Result<(), Box<dyn Error>> {
+    let input = fs::read_to_string(path).map_err(|e| format!("Failed to read {path}: {e}"))?;
+    let answers = solution.solve(&input, part)?;
+
+    if let Some(answer) = answers.part1 {
+        println!("Part 1: {answer}");
+    }
+    if let Some(answer) = answers.part2 {
+        println!("Part 2: {answer}");
+    }
+    Ok(())
+}
+
+fn run(args: Args) -> Result<(), Box<dyn Error>> {
+    let many = args.days.len() > 1;
+    for solution in args.days {
+        let path = match &args.input {
+            Some(path) => path.clone(),
+            None => format!("data/day{}.input", solution.day),
+        };
+
+        if many {
+            println!("Day {:02}", solution.day);
+        }
+        run_day(solution, args.part, &path)?;
+    }
+    Ok(())
+}
+
+fn main() {
+    let result = parse_args(env::args().skip(1)).and_then(run);
+    if let Err(e) = result {
+        eprintln!("{e}");
+        process::exit(1);
+    }
+}
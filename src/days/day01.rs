@@ -1,24 +1,28 @@
+use crate::error::parse_token;
+use crate::{AocError, Solution};
 use std::collections::HashMap;
-use std::{error::Error, fs};
 
-fn parse_input(content: &str) -> (Vec<i32>, Vec<i32>) {
+fn parse_input(content: &str) -> Result<(Vec<i32>, Vec<i32>), AocError> {
     let mut col1: Vec<i32> = Vec::new();
     let mut col2: Vec<i32> = Vec::new();
 
-    for line in content.lines() {
+    for (i, line) in content.lines().enumerate() {
         let values: Vec<i32> = line
             .split_whitespace()
-            .map(|s| s.parse().unwrap())
-            .collect();
-
-        col1.push(values[0]);
-        col2.push(values[1]);
+            .map(|s| parse_token(i, line, s))
+            .collect::<Result<_, _>>()?;
+
+        let [a, b] = values[..] else {
+            return Err(AocError::parse(i + 1, 1, "Expected two numbers"));
+        };
+        col1.push(a);
+        col2.push(b);
     }
 
     col1.sort();
     col2.sort();
 
-    (col1, col2)
+    Ok((col1, col2))
 }
 
 fn count(values: &[i32]) -> HashMap<i32, i32> {
@@ -34,9 +38,7 @@ fn count(values: &[i32]) -> HashMap<i32, i32> {
     // map
 }
 
-fn part1(content: &str) -> i32 {
-    let (col1, col2) = parse_input(content);
-
+fn part1((col1, col2): &(Vec<i32>, Vec<i32>)) -> i32 {
     // // Original Approach
     // let mut distance: i32 = 0;
     // for (&val1, &val2) in col1.iter().zip(col2.iter()) {
@@ -49,12 +51,10 @@ fn part1(content: &str) -> i32 {
         .sum()
 }
 
-fn part2(content: &str) -> i32 {
-    let (col1, col2) = parse_input(content);
-
+fn part2((col1, col2): &(Vec<i32>, Vec<i32>)) -> i32 {
     // Compute similarity
-    let counter1 = count(&col1);
-    let counter2 = count(&col2);
+    let counter1 = count(col1);
+    let counter2 = count(col2);
 
     // // Original Approach
     // let counter1 = count(&col1);
@@ -72,23 +72,34 @@ fn part2(content: &str) -> i32 {
         .sum()
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    // let path: &str = "data/day1.sample";
-    let path: &str = "data/day1.input";
-    let content = fs::read_to_string(path)?;
-    println!("Part 1: {:?}", part1(&content));
-    println!("Part 2: {:?}", part2(&content));
-    Ok(())
+pub struct Day01;
+
+impl Solution for Day01 {
+    type Input = (Vec<i32>, Vec<i32>);
+    type Answer1 = i32;
+    type Answer2 = i32;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<i32, AocError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<i32, AocError> {
+        Ok(part2(input))
+    }
 }
 
 #[test]
 fn test_part1() {
     let input = ["3   4", "4   3", "2   5", "1   3", "3   9", "3   3"].join("\n");
-    assert_eq!(part1(&input), 11);
+    assert_eq!(part1(&parse_input(&input).unwrap()), 11);
 }
 
 #[test]
 fn test_part2() {
     let input = ["3   4", "4   3", "2   5", "1   3", "3   9", "3   3"].join("\n");
-    assert_eq!(part2(&input), 31);
+    assert_eq!(part2(&parse_input(&input).unwrap()), 31);
 }
@@ -1,11 +1,13 @@
-use std::{error::Error, fs};
+use crate::error::parse_token;
+use crate::{AocError, Solution};
 
-fn parse_input(input: &str) -> Vec<Vec<i32>> {
+fn parse_input(input: &str) -> Result<Vec<Vec<i32>>, AocError> {
     input
         .lines()
-        .map(|line| {
+        .enumerate()
+        .map(|(i, line)| {
             line.split_whitespace()
-                .map(|s| s.parse().unwrap())
+                .map(|s| parse_token(i, line, s))
                 .collect()
         })
         .collect()
@@ -16,7 +18,7 @@ fn is_safe(report: &[i32]) -> bool {
         report.windows(2).all(|w| w[0] <= w[1]) || report.windows(2).all(|w| w[0] >= w[1]);
     let valid_diffs = report.windows(2).all(|w| {
         let diff = (w[0] - w[1]).abs();
-        diff >= 1 && diff <= 3
+        (1..=3).contains(&diff)
     });
     monotonic && valid_diffs
 }
@@ -33,26 +35,35 @@ fn problem_dampener(report: &[i32]) -> bool {
     (0..report.len()).any(|i| is_safe(&hold_out(report, i)))
 }
 
-fn part1(input: &str) -> usize {
-    parse_input(input)
-        .iter()
-        .filter(|&report| is_safe(report))
-        .count()
+fn part1(reports: &[Vec<i32>]) -> usize {
+    reports.iter().filter(|&report| is_safe(report)).count()
 }
 
-fn part2(input: &str) -> usize {
-    parse_input(input)
+fn part2(reports: &[Vec<i32>]) -> usize {
+    reports
         .iter()
         .filter(|&report| problem_dampener(report))
         .count()
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let path: &str = "data/day2.input";
-    let input = fs::read_to_string(path)?;
-    println!("Part 1: {:?}", part1(&input));
-    println!("Part 2: {:?}", part2(&input));
-    Ok(())
+pub struct Day02;
+
+impl Solution for Day02 {
+    type Input = Vec<Vec<i32>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(part2(input))
+    }
 }
 
 #[test]
@@ -66,7 +77,7 @@ fn test_part1() {
         "1 3 6 7 9",
     ]
     .join("\n");
-    assert_eq!(part1(&input), 2);
+    assert_eq!(part1(&parse_input(&input).unwrap()), 2);
 }
 
 #[test]
@@ -80,5 +91,5 @@ fn test_part2() {
         "1 3 6 7 9",
     ]
     .join("\n");
-    assert_eq!(part2(&input), 4);
+    assert_eq!(part2(&parse_input(&input).unwrap()), 4);
 }
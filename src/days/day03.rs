@@ -1,14 +1,14 @@
+use crate::{AocError, Solution};
 use atoi::atoi;
-use std::{error::Error, fs};
 
-enum Command {
-    Mul(i32),
+pub enum Command {
+    Mul(usize),
     Do,
     Dont,
 }
 
 // Parse a number of up to 3 digits, returning (number, chars consumed)
-fn parse_number(bytes: &[u8]) -> Option<(i32, usize)> {
+fn parse_number(bytes: &[u8]) -> Option<(usize, usize)> {
     let len = bytes
         .iter()
         .take_while(|&&b| b.is_ascii_digit())
@@ -58,57 +58,66 @@ fn try_parse_command(bytes: &[u8]) -> Option<(Command, usize)> {
     try_parse_mul(bytes)
 }
 
-fn part1(input: &str) -> usize {
+fn parse_input(input: &str) -> Vec<Command> {
     let bytes = input.as_bytes();
 
-    let sum: i32 = (0..bytes.len())
+    (0..bytes.len())
         .filter_map(|i| try_parse_command(&bytes[i..]))
-        .fold(0, |sum, (command, _)| match command {
-            Command::Mul(result) => sum + result,
-            _ => sum,
-        });
-
-    sum.try_into().unwrap()
+        .map(|(command, _)| command)
+        .collect()
 }
 
-fn part2(input: &str) -> usize {
-    let bytes = input.as_bytes();
+fn part1(commands: &[Command]) -> usize {
+    commands.iter().fold(0, |sum, command| match command {
+        Command::Mul(result) => sum + result,
+        _ => sum,
+    })
+}
 
+fn part2(commands: &[Command]) -> usize {
     let mut enabled = true;
-    let sum: i32 = (0..bytes.len())
-        .filter_map(|i| try_parse_command(&bytes[i..]))
-        .fold(0, |sum, (command, _)| match command {
-            Command::Mul(result) if enabled => sum + result,
-            Command::Do => {
-                enabled = true;
-                sum
-            }
-            Command::Dont => {
-                enabled = false;
-                sum
-            }
-            _ => sum,
-        });
-
-    sum.try_into().unwrap()
+    commands.iter().fold(0, |sum, command| match command {
+        Command::Mul(result) if enabled => sum + result,
+        Command::Do => {
+            enabled = true;
+            sum
+        }
+        Command::Dont => {
+            enabled = false;
+            sum
+        }
+        _ => sum,
+    })
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let path: &str = "data/day3.input";
-    let input = fs::read_to_string(path)?;
-    println!("Part 1: {:?}", part1(&input));
-    println!("Part 2: {:?}", part2(&input));
-    Ok(())
+pub struct Day03;
+
+impl Solution for Day03 {
+    type Input = Vec<Command>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Ok(parse_input(input))
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(part2(input))
+    }
 }
 
 #[test]
 fn test_part1() {
     let input = "xmul(2,4)%&mul[3,7]!@^do_not_mul(5,5)+mul(32,64]then(mul(11,8)mul(8,5))";
-    assert_eq!(part1(&input), 161);
+    assert_eq!(part1(&parse_input(input)), 161);
 }
 
 #[test]
 fn test_part2() {
     let input = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
-    assert_eq!(part2(&input), 48);
+    assert_eq!(part2(&parse_input(input)), 48);
 }
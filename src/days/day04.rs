@@ -1,75 +1,33 @@
+use crate::{AocError, Direction, Grid, Point, Solution};
 use itertools::iproduct;
 use itertools::multizip;
-use std::{error::Error, fs};
-
-#[derive(Debug, Copy, Clone, Eq, PartialEq)]
-enum Direction {
-    UP,
-    DOWN,
-    LEFT,
-    RIGHT,
-    UR,
-    UL,
-    DR,
-    DL,
-}
 
-impl Direction {
-    fn offset(&self) -> (i32, i32) {
-        match self {
-            Direction::UP => (0, -3),
-            Direction::DOWN => (0, 3),
-            Direction::LEFT => (-3, 0),
-            Direction::RIGHT => (3, 0),
-            Direction::UR => (3, -3),
-            Direction::UL => (-3, -3),
-            Direction::DR => (3, 3),
-            Direction::DL => (-3, 3),
-        }
-    }
-    // Get all possible directions
-    fn all() -> &'static [Direction] {
-        &[
-            Direction::UP,
-            Direction::DOWN,
-            Direction::LEFT,
-            Direction::RIGHT,
-            Direction::UR,
-            Direction::UL,
-            Direction::DR,
-            Direction::DL,
-        ]
-    }
+fn parse_input(input: &str) -> Result<Vec<Vec<char>>, AocError> {
+    // Parse through `Grid` so that ragged lines are rejected
+    let grid = Grid::parse_str(input, Ok, '.')?;
+    Ok((0..grid.height)
+        .map(|y| grid.iter_row(y).copied().collect())
+        .collect())
 }
 
-fn parse_input(input: &str) -> Vec<Vec<char>> {
-    input.lines().map(|line| line.chars().collect()).collect()
+fn width<T>(grid: &[Vec<T>]) -> usize {
+    grid.first().map_or(0, Vec::len)
 }
 
 fn is_in_bounds<T>(grid: &[Vec<T>], row: i32, col: i32) -> bool {
-    row >= 0 && col >= 0 && row < grid.len() as i32 && col < grid[0].len() as i32
+    row >= 0 && col >= 0 && row < grid.len() as i32 && col < width(grid) as i32
 }
 
 fn search_xmas(grid: &[Vec<char>], i: usize, j: usize, direction: Direction) -> bool {
-    let (x, y) = direction.offset();
-
-    if !is_in_bounds(grid, (i as i32) + x, (j as i32) + y) {
-        return false;
-    }
-
-    let row_step = x / 3;
-    let col_step = y / 3;
     const XMAS: [char; 4] = ['X', 'M', 'A', 'S'];
-    for k in 0..4 {
-        let row_index = ((i as i32) + k * row_step) as usize;
-        let col_index = ((j as i32) + k * col_step) as usize;
-
-        if grid[row_index][col_index] != XMAS[k as usize] {
-            return false;
-        }
-    }
-
-    true
+    let start = Point::new(j, i);
+
+    (0..4).all(|k| {
+        start
+            .checked_add_signed(direction.offset() * k as isize)
+            .and_then(|p| grid.get(p.y)?.get(p.x))
+            == Some(&XMAS[k])
+    })
 }
 
 fn search_double_mas(grid: &[Vec<char>], i: usize, j: usize) -> bool {
@@ -110,26 +68,36 @@ fn search_double_mas(grid: &[Vec<char>], i: usize, j: usize) -> bool {
     found_diagonal && found_antidiagonal
 }
 
-fn part1(input: &str) -> usize {
-    let grid = parse_input(input);
-    iproduct!(0..grid.len(), 0..grid[0].len(), Direction::all())
-        .filter(|&(i, j, direction)| search_xmas(&grid, i, j, *direction))
+fn part1(grid: &[Vec<char>]) -> usize {
+    iproduct!(0..grid.len(), 0..width(grid), Direction::ALL)
+        .filter(|&(i, j, direction)| search_xmas(grid, i, j, direction))
         .count()
 }
 
-fn part2(input: &str) -> usize {
-    let grid = parse_input(input);
-    iproduct!(0..grid.len(), 0..grid[0].len())
-        .filter(|&(i, j)| search_double_mas(&grid, i, j))
+fn part2(grid: &[Vec<char>]) -> usize {
+    iproduct!(0..grid.len(), 0..width(grid))
+        .filter(|&(i, j)| search_double_mas(grid, i, j))
         .count()
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let path: &str = "data/day4.input";
-    let input = fs::read_to_string(path)?;
-    println!("Part 1: {:?}", part1(&input));
-    println!("Part 2: {:?}", part2(&input));
-    Ok(())
+pub struct Day04;
+
+impl Solution for Day04 {
+    type Input = Vec<Vec<char>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(part2(input))
+    }
 }
 
 #[test]
@@ -147,7 +115,7 @@ fn test_part1() {
         "MXMXAXMASX",
     ]
     .join("\n");
-    assert_eq!(part1(&input), 18);
+    assert_eq!(part1(&parse_input(&input).unwrap()), 18);
 }
 
 #[test]
@@ -165,5 +133,5 @@ fn test_part2() {
         "MXMXAXMASX",
     ]
     .join("\n");
-    assert_eq!(part2(&input), 9);
+    assert_eq!(part2(&parse_input(&input).unwrap()), 9);
 }
@@ -0,0 +1,178 @@
+use crate::error::parse_token;
+use crate::{AocError, Graph, Solution};
+use itertools::Itertools;
+
+type ParserOutput = (Vec<(u32, u32)>, Vec<Vec<u32>>);
+
+fn parse_input(input: &str) -> Result<ParserOutput, AocError> {
+    // Split content into two parts on double newline
+    let (rules_str, pages_str) = input.split_once("\n\n").unwrap_or((input, ""));
+
+    // Parse the rules
+    let rules = rules_str
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let (before, after) = line
+                .split('|')
+                .collect_tuple()
+                .ok_or_else(|| AocError::parse(i + 1, 1, "Expected a rule like 47|53"))?;
+            Ok((parse_token(i, line, before)?, parse_token(i, line, after)?))
+        })
+        .collect::<Result<_, AocError>>()?;
+
+    // Parse the page orders, which start after the rules and the blank line
+    let offset = rules_str.lines().count() + 1;
+    let pages = pages_str
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            line.split(',')
+                .map(|s| parse_token(offset + i, line, s))
+                .collect()
+        })
+        .collect::<Result<_, _>>()?;
+
+    Ok((rules, pages))
+}
+
+fn is_relevant_rule(rule: (u32, u32), pages: &[u32]) -> bool {
+    pages.contains(&rule.0) && pages.contains(&rule.1)
+}
+
+fn sort_by_rules(rules: Vec<(u32, u32)>, pages: Vec<u32>) -> Result<Vec<u32>, AocError> {
+    let mut graph: Graph<u32> = Graph::directed();
+
+    // Insert all pages first
+    for page in &pages {
+        graph.add_node(*page);
+    }
+
+    // Add dependencies from relevant rules
+    for (before, after) in rules.iter().filter(|rule| is_relevant_rule(**rule, &pages)) {
+        graph.add_edge(*before, *after);
+    }
+
+    // Concatenate the layers of pages that only depend on earlier layers
+    Ok(graph.topological_layers()?.concat())
+}
+
+fn get_midpoint(values: &[u32]) -> u32 {
+    values[values.len() / 2]
+}
+
+fn part1((rules, orders): &(Vec<(u32, u32)>, Vec<Vec<u32>>)) -> Result<u32, AocError> {
+    orders
+        .iter()
+        .map(|original_order| {
+            let sorted_order = sort_by_rules(rules.clone(), original_order.clone())?;
+            if *original_order == sorted_order {
+                Ok(get_midpoint(&sorted_order))
+            } else {
+                Ok(0)
+            }
+        })
+        .sum()
+}
+
+fn part2((rules, orders): &(Vec<(u32, u32)>, Vec<Vec<u32>>)) -> Result<u32, AocError> {
+    orders
+        .iter()
+        .map(|original_order| {
+            let sorted_order = sort_by_rules(rules.clone(), original_order.clone())?;
+            if *original_order == sorted_order {
+                Ok(0)
+            } else {
+                Ok(get_midpoint(&sorted_order))
+            }
+        })
+        .sum()
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    type Input = ParserOutput;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<u32, AocError> {
+        part1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<u32, AocError> {
+        part2(input)
+    }
+}
+
+#[test]
+fn test_part1() {
+    let input = r#"47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47"#;
+
+    assert_eq!(part1(&parse_input(input).unwrap()).unwrap(), 143);
+}
+
+#[test]
+fn test_part2() {
+    let input = r#"47|53
+97|13
+97|61
+97|47
+75|29
+61|13
+75|53
+29|13
+97|29
+53|29
+61|53
+97|53
+61|29
+47|13
+75|47
+97|75
+47|61
+75|61
+47|29
+75|13
+53|13
+
+75,47,61,53,29
+97,61,53,29,13
+75,29,13
+75,97,47,61,53
+61,13,29
+97,13,75,29,47"#;
+
+    assert_eq!(part2(&parse_input(input).unwrap()).unwrap(), 123);
+}
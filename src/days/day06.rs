@@ -0,0 +1,273 @@
+use crate::{AocError, Direction, Directions, Grid, Point, Solution};
+
+#[derive(Copy, Clone, Debug)]
+pub struct GuardState {
+    position: Point<usize>,
+    direction: Direction,
+}
+
+#[derive(Clone)]
+pub struct Map {
+    occupancy: Grid<bool>,
+    visited: Grid<Directions>,
+}
+
+impl Map {
+    fn from_str(input: &str) -> Result<(Map, GuardState), AocError> {
+        let tiles = Grid::parse_str(
+            input,
+            |c| match c {
+                '.' | '#' | '^' | 'v' | '<' | '>' => Ok(c),
+                _ => Err("expected '.', '#' or a guard".to_string()),
+            },
+            '.',
+        )?;
+
+        let mut occupancy = Grid::new(tiles.width, tiles.height, false);
+        let mut guard_start: Option<GuardState> = None;
+
+        for (xy, &ch) in tiles.iter_items() {
+            match Direction::from_char(ch) {
+                Some(direction) => {
+                    guard_start = Some(GuardState {
+                        position: Point::from(xy),
+                        direction,
+                    })
+                }
+                None => occupancy[xy] = ch == '#',
+            }
+        }
+
+        let visited = Grid::new(occupancy.width, occupancy.height, Directions::empty());
+
+        let guard_start = guard_start.ok_or(AocError::Missing("guard"))?;
+        Ok((Map { occupancy, visited }, guard_start))
+    }
+
+    fn width(&self) -> usize {
+        self.occupancy.width
+    }
+    fn height(&self) -> usize {
+        self.occupancy.height
+    }
+    fn is_occupied(&self, x: usize, y: usize) -> bool {
+        self.occupancy[(x, y)]
+    }
+    /// The neighbouring cell in `dir`, or `None` if it is outside the room.
+    fn step(&self, position: Point<usize>, dir: &Direction) -> Option<Point<usize>> {
+        let next = position.checked_add_signed(dir.offset())?;
+        self.occupancy.checked_xy(next).map(Point::from)
+    }
+    fn visit(&mut self, x: usize, y: usize, dir: &Direction) {
+        self.visited[(x, y)] |= dir.flag();
+    }
+
+    fn is_visited(&self, x: usize, y: usize) -> bool {
+        !self.visited[(x, y)].is_empty()
+    }
+
+    fn is_visited_in_direction(&self, x: usize, y: usize, dir: &Direction) -> bool {
+        self.visited[(x, y)].contains(dir.flag())
+    }
+
+    fn add_obstacle(&mut self, x: usize, y: usize) {
+        self.occupancy[(x, y)] = true;
+    }
+}
+
+struct Guard<'a> {
+    position: Point<usize>,
+    map: &'a mut Map,
+    direction: Direction,
+}
+
+impl<'a> Guard<'a> {
+    fn new(position: Point<usize>, map: &'a mut Map, direction: Direction) -> Guard<'a> {
+        map.visit(position.x, position.y, &direction);
+        Guard {
+            position,
+            map,
+            direction,
+        }
+    }
+
+    /// Where the guard would be, and facing, after the next step, or `None` if it would leave
+    /// the room.
+    fn peek_step(&mut self) -> Option<(Point<usize>, Direction)> {
+        let next = self.map.step(self.position, &self.direction)?;
+        if self.map.is_occupied(next.x, next.y) {
+            Some((next, self.direction.turn_right()))
+        } else {
+            Some((next, self.direction))
+        }
+    }
+
+    fn step(&mut self) -> bool {
+        let Some(next) = self.map.step(self.position, &self.direction) else {
+            // Leaving the extents of the room
+            return false;
+        };
+        if self.map.is_occupied(next.x, next.y) {
+            self.direction = self.direction.turn_right();
+        } else {
+            self.position = next;
+            self.map.visit(next.x, next.y, &self.direction);
+        }
+        true
+    }
+}
+
+impl std::fmt::Debug for Guard<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f)?;
+        for y in 0..self.map.occupancy.height {
+            for x in 0..self.map.occupancy.width {
+                if Point::new(x, y) == self.position {
+                    write!(f, "{}", self.direction)?;
+                } else {
+                    write!(f, "{}", if self.map.occupancy[(x, y)] { '#' } else { '.' })?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for Map {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f)?;
+        for y in 0..self.occupancy.height {
+            for x in 0..self.occupancy.width {
+                if self.is_occupied(x, y) {
+                    write!(f, "#")?;
+                } else {
+                    write!(f, "{}", if self.is_visited(x, y) { 'x' } else { '.' })?;
+                }
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Guard<'_> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "Guard at ({}, {}) facing {}",
+            self.position.x, self.position.y, self.direction
+        )
+    }
+}
+
+fn part1((map, start): &(Map, GuardState)) -> usize {
+    let mut map = map.clone();
+
+    let mut guard = Guard::new(start.position, &mut map, start.direction);
+    let mut is_in_room: bool = true;
+    while is_in_room {
+        is_in_room = guard.step();
+    }
+    map.visited.iter().filter(|&x| !x.is_empty()).count()
+}
+
+fn check_if_would_loop_if_obstacle(
+    x: usize,
+    y: usize,
+    map: &Map,
+    guard_start: &GuardState,
+) -> Option<(usize, usize)> {
+    let mut map = map.clone();
+    map.add_obstacle(x, y);
+    let mut guard = Guard::new(guard_start.position, &mut map, guard_start.direction);
+    loop {
+        let (next, dir_next) = guard.peek_step()?;
+        if guard.map.is_visited_in_direction(next.x, next.y, &dir_next) {
+            return Some(next.into());
+        }
+        guard.step();
+    }
+}
+
+fn part2((map, start): &(Map, GuardState)) -> usize {
+    (0..map.width())
+        .flat_map(|x| (0..map.height()).map(move |y| (x, y)))
+        .filter(|&(x, y)| start.position != Point::new(x, y))
+        .filter(|(x, y)| !map.is_occupied(*x, *y))
+        .filter_map(|(x, y)| check_if_would_loop_if_obstacle(x, y, map, start))
+        .count()
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    type Input = (Map, GuardState);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Map::from_str(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(part2(input))
+    }
+}
+
+#[test]
+fn test_part1() {
+    let input = r#"....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#..."#;
+
+    assert_eq!(part1(&Map::from_str(input).unwrap()), 41);
+}
+
+#[test]
+fn test_part2() {
+    let input = r#"....#.....
+.........#
+..........
+..#.......
+.......#..
+..........
+.#..^.....
+........#.
+#.........
+......#..."#;
+
+    assert_eq!(part2(&Map::from_str(input).unwrap()), 6);
+}
+
+#[test]
+fn test_invalid_input() {
+    assert_eq!(Map::from_str("").unwrap_err(), AocError::Missing("guard"));
+    assert_eq!(
+        Map::from_str("..#\n...").unwrap_err(),
+        AocError::Missing("guard")
+    );
+    assert!(matches!(
+        Map::from_str("..#\n.x^"),
+        Err(AocError::Parse {
+            line: 2,
+            column: 2,
+            ..
+        })
+    ));
+    assert!(matches!(
+        Map::from_str("..#\n.^"),
+        Err(AocError::Parse { line: 2, .. })
+    ));
+}
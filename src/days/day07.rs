@@ -1,12 +1,14 @@
+use crate::error::parse_token;
+use crate::{AocError, Solution};
 use itertools::repeat_n;
 use itertools::Itertools;
 use rayon::prelude::*;
-use std::{error::Error, fs, iter::successors};
+use std::iter::successors;
 
 // https://stackoverflow.com/a/69302957
 // Key idea-- then() returns an Option, so this ends when the value is smaller than 10.
 fn digits(n: u64) -> u32 {
-    successors(Some(n), |&n| (n >= 10).then(|| n / 10)).count() as u32
+    successors(Some(n), |&n| (n >= 10).then_some(n / 10)).count() as u32
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -40,24 +42,28 @@ impl std::fmt::Display for Operator {
     }
 }
 
-fn parse_input(input: &str) -> Vec<(u64, Vec<u64>)> {
+fn parse_input(input: &str) -> Result<Vec<(u64, Vec<u64>)>, AocError> {
     input
         .lines()
-        .map(|line| {
-            let (a, rest) = line.split_once(':').unwrap();
-            (
-                a.parse().unwrap(),
+        .enumerate()
+        .map(|(i, line)| {
+            let (a, rest) = line
+                .split_once(':')
+                .ok_or_else(|| AocError::parse(i + 1, 1, "Expected a ':'"))?;
+            Ok((
+                parse_token(i, line, a)?,
                 rest.split_whitespace()
-                    .map(|s| s.parse().unwrap())
-                    .collect(),
-            )
+                    .map(|s| parse_token(i, line, s))
+                    .collect::<Result<_, _>>()?,
+            ))
         })
         .collect()
 }
 
 fn find_answer(result: &u64, values: &[u64], ops: &[Operator]) -> bool {
-    let first = values[0];
-    let rest: &[u64] = &values[1..];
+    let Some((&first, rest)) = values.split_first() else {
+        return false;
+    };
 
     repeat_n(ops.iter(), rest.len())
         .multi_cartesian_product()
@@ -69,23 +75,35 @@ fn find_answer(result: &u64, values: &[u64], ops: &[Operator]) -> bool {
         })
 }
 
-fn part(path: &str, ops: &[Operator]) -> u64 {
-    parse_input(path)
+fn part(equations: &[(u64, Vec<u64>)], ops: &[Operator]) -> u64 {
+    equations
         .par_iter()
-        .filter(|(result, values)| find_answer(result, values, &ops))
+        .filter(|(result, values)| find_answer(result, values, ops))
         .map(|(a, _)| a)
         .sum()
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let path = "data/day7.input";
-    let input = fs::read_to_string(path)?;
-    let ops_part1 = vec![Operator::Add, Operator::Multiply];
-    println!("Part 1: {:?}", part(&input, &ops_part1));
+pub struct Day07;
 
-    let ops_part2 = vec![Operator::Add, Operator::Multiply, Operator::Concat];
-    println!("Part 2: {:?}", part(&input, &ops_part2));
-    Ok(())
+impl Solution for Day07 {
+    type Input = Vec<(u64, Vec<u64>)>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<u64, AocError> {
+        Ok(part(input, &[Operator::Add, Operator::Multiply]))
+    }
+
+    fn part2(input: &Self::Input) -> Result<u64, AocError> {
+        Ok(part(
+            input,
+            &[Operator::Add, Operator::Multiply, Operator::Concat],
+        ))
+    }
 }
 
 #[test]
@@ -100,7 +118,7 @@ fn test_part1() {
 21037: 9 7 18 13
 292: 11 6 16 20"#;
     let ops_part1 = vec![Operator::Add, Operator::Multiply];
-    assert_eq!(part(&input, &ops_part1), 3749);
+    assert_eq!(part(&parse_input(input).unwrap(), &ops_part1), 3749);
 }
 
 #[test]
@@ -116,5 +134,5 @@ fn test_part2() {
 292: 11 6 16 20"#;
     let ops_part2 = vec![Operator::Add, Operator::Multiply, Operator::Concat];
 
-    assert_eq!(part(&input, &ops_part2), 11387);
+    assert_eq!(part(&parse_input(input).unwrap(), &ops_part2), 11387);
 }
@@ -0,0 +1,108 @@
+use crate::{AocError, Grid, Point, Solution};
+use itertools::Itertools;
+
+type Position = Point<i32>;
+
+fn parse_input(input: &str) -> Result<Grid<char>, AocError> {
+    Grid::parse_str(input, Ok, '.')
+}
+
+fn get_antinodes(a: Position, b: Position, antennas: &Grid<char>, resonant: bool) -> Vec<Position> {
+    let delta = a - b;
+    let grid_size = antennas.height.max(antennas.width) as i32;
+
+    #[rustfmt::skip]
+    let search_k = if resonant {-grid_size..=grid_size} else {1..=1};
+
+    search_k
+        .flat_map(|k| {
+            let delta_k = delta * k;
+            [a + delta_k, b - delta_k]
+        })
+        .filter(|&p| antennas.checked_xy(p).is_some())
+        .collect()
+}
+
+fn find_antinodes_for_freq(antennas: &Grid<char>, freq: char, resonant: bool) -> Vec<Position> {
+    let positions: Vec<Position> = antennas
+        .iter_items()
+        .filter(|(_, &c)| c == freq)
+        .map(|((x, y), _)| Position::new(x as i32, y as i32))
+        .collect();
+
+    let antinodes: Vec<Position> = positions
+        .iter()
+        .tuple_combinations()
+        .flat_map(|(&a, &b)| get_antinodes(a, b, antennas, resonant))
+        .unique()
+        .collect();
+
+    antinodes
+}
+
+fn problem(antennas: &Grid<char>, resonant: bool) -> usize {
+    antennas
+        .iter()
+        .unique()
+        .filter(|&c| c != &'.' && c != &'\n')
+        .copied()
+        .flat_map(|freq| find_antinodes_for_freq(antennas, freq, resonant))
+        .unique()
+        .count()
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    type Input = Grid<char>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(problem(input, false))
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(problem(input, true))
+    }
+}
+
+#[test]
+fn test_part1() {
+    let input = r#"............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............"#;
+
+    assert_eq!(problem(&parse_input(input).unwrap(), false), 14);
+}
+
+#[test]
+fn test_part2() {
+    let input = r#"............
+........0...
+.....0......
+.......0....
+....0.......
+......A.....
+............
+............
+........A...
+.........A..
+............
+............"#;
+
+    assert_eq!(problem(&parse_input(input).unwrap(), true), 34);
+}
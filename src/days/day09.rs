@@ -1,10 +1,11 @@
-use std::{error::Error, fs};
+use crate::{AocError, Solution};
 
 use itertools::Itertools;
 
 #[derive(Debug)]
 struct Block {
-    id: String,
+    /// The file's id, or `None` for free space.
+    id: Option<usize>,
     start: usize,
     width: usize,
 }
@@ -18,30 +19,33 @@ fn parse_input(input: &str) -> Vec<String> {
         .collect()
 }
 
-fn decode(encoded: &[String]) -> Vec<String> {
-    encoded
+fn decode(encoded: &[String]) -> Result<Vec<Option<usize>>, AocError> {
+    let blocks = encoded
         .iter()
         .enumerate()
         .map(|(k, num_str)| {
-            let n = num_str.parse::<usize>().unwrap();
-            match k % 2 {
-                0 => vec![(k / 2).to_string(); n],
-                _ => vec!['.'.to_string(); n],
-            }
+            let n = num_str
+                .parse::<usize>()
+                .map_err(|_| AocError::parse(1, k + 1, format!("Invalid digit: {num_str}")))?;
+            Ok(match k % 2 {
+                0 => vec![Some(k / 2); n],
+                _ => vec![None; n],
+            })
         })
-        .flatten()
-        .collect()
+        .collect::<Result<Vec<_>, AocError>>()?;
+
+    Ok(blocks.into_iter().flatten().collect())
 }
 
-fn compress(data: &mut Vec<String>) -> &mut Vec<String> {
+fn compress(data: &mut [Option<usize>]) -> &mut [Option<usize>] {
     let mut left: usize = 0;
     let mut right: usize = data.len().saturating_sub(1);
     while left < right {
-        while right > left && data[right] == "." {
+        while right > left && data[right].is_none() {
             right = right.saturating_sub(1);
         }
 
-        while left < right && data[left] != "." {
+        while left < right && data[left].is_some() {
             left += 1;
         }
 
@@ -55,26 +59,26 @@ fn compress(data: &mut Vec<String>) -> &mut Vec<String> {
     data
 }
 
-fn compress2(data: &[String]) -> Vec<String> {
+fn compress2(data: &[Option<usize>]) -> Vec<Option<usize>> {
     let mut free_blocks: Vec<Block> = Vec::new();
     let mut claimed_blocks: Vec<Block> = Vec::new();
 
     let mut k = 0;
     while k < data.len() {
         let mut block_width = 0;
-        let block_id = &data[k];
+        let block_id = data[k];
 
         // Find the block width
-        while (k + block_width) < data.len() && data[k + block_width] == *block_id {
+        while (k + block_width) < data.len() && data[k + block_width] == block_id {
             block_width += 1;
         }
 
         let block = Block {
-            id: block_id.to_string(),
+            id: block_id,
             start: k,
             width: block_width,
         };
-        if block_id == "." {
+        if block_id.is_none() {
             free_blocks.push(block);
         } else {
             claimed_blocks.push(block);
@@ -83,13 +87,13 @@ fn compress2(data: &[String]) -> Vec<String> {
         k += block_width;
     }
 
-    claimed_blocks.sort_by_key(|b| std::cmp::Reverse(b.id.parse::<usize>().unwrap()));
+    claimed_blocks.sort_by_key(|b| std::cmp::Reverse(b.id));
 
     for block in claimed_blocks.iter_mut() {
         for (k, maybe) in free_blocks.iter_mut().enumerate() {
             if block.width <= maybe.width && block.start > maybe.start {
                 let new_free_block = Block {
-                    id: ".".to_string(),
+                    id: None,
                     start: block.start,
                     width: block.width,
                 };
@@ -110,51 +114,57 @@ fn compress2(data: &[String]) -> Vec<String> {
         .iter()
         .chain(claimed_blocks.iter())
         .sorted_by_key(|x| x.start)
-        .map(|x| vec![x.id.clone(); x.width])
-        .flatten()
+        .flat_map(|x| vec![x.id; x.width])
         .collect()
 }
 
-fn checksum(compressed: &[String]) -> usize {
+fn checksum(compressed: &[Option<usize>]) -> usize {
     compressed
         .iter()
         .enumerate()
-        .filter(|(_, b)| *b != ".")
-        .map(|(k, data)| k * data.parse::<usize>().unwrap())
+        .filter_map(|(k, id)| id.map(|id| k * id))
         .sum()
 }
 
-fn part1(input: &str) -> usize {
-    let encoded = parse_input(&input);
-    let mut data = decode(&encoded);
+fn part1(data: &[Option<usize>]) -> usize {
+    let mut data = data.to_vec();
     let compressed = compress(&mut data);
     checksum(compressed)
 }
 
-fn part2(input: &str) -> usize {
-    let encoded = parse_input(&input);
-    let data = decode(&encoded);
-    let compressed = compress2(&data);
+fn part2(data: &[Option<usize>]) -> usize {
+    let compressed = compress2(data);
     checksum(&compressed)
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let path = "data/day9.input";
-    let input = fs::read_to_string(path)?;
+pub struct Day09;
 
-    println!("Part 1: {}", part1(&input));
-    println!("Part 2: {}", part2(&input));
-    Ok(())
+impl Solution for Day09 {
+    type Input = Vec<Option<usize>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        decode(&parse_input(input))
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(part2(input))
+    }
 }
 
 #[test]
 fn test_part1() {
     let input = "2333133121414131402";
-    assert_eq!(part1(&input), 1928);
+    assert_eq!(part1(&decode(&parse_input(input)).unwrap()), 1928);
 }
 
 #[test]
 fn test_part2() {
     let input = "2333133121414131402";
-    assert_eq!(part2(&input), 2858);
+    assert_eq!(part2(&decode(&parse_input(input)).unwrap()), 2858);
 }
@@ -0,0 +1,127 @@
+use crate::{AocError, Direction, Graph, Grid, Solution};
+use std::collections::HashMap;
+
+const SUMMIT_HEIGHT: u8 = 9;
+const TRAILHEAD_HEIGHT: u8 = 0;
+
+fn parse_input(input: &str) -> Result<Grid<u8>, AocError> {
+    Grid::parse_str(
+        input,
+        |c| {
+            c.to_digit(10)
+                .map(|d| d as u8)
+                .ok_or_else(|| format!("Invalid height: {c}"))
+        },
+        0,
+    )
+}
+type Position = (usize, usize);
+
+/// Directed edges from each cell to the orthogonal neighbours exactly one higher.
+fn to_graph(values: &Grid<u8>) -> Graph<Position> {
+    let mut graph = Graph::directed();
+    for (xy, &height) in values.iter_items() {
+        graph.add_node(xy);
+        for next in Direction::cardinal().filter_map(|d| values.step(xy, d)) {
+            if values[next] == height + 1 {
+                graph.add_edge(xy, next);
+            }
+        }
+    }
+    graph
+}
+
+/// Number of summits reachable from each trailhead, summed.
+fn score(values: &Grid<u8>, graph: &Graph<Position>, trailheads: &[Position]) -> usize {
+    trailheads
+        .iter()
+        .map(|&start| {
+            graph
+                .bfs(start)
+                .filter(|&xy| values[xy] == SUMMIT_HEIGHT)
+                .count()
+        })
+        .sum()
+}
+
+/// Number of distinct trails from any trailhead to any summit.
+fn rating(values: &Grid<u8>, graph: &Graph<Position>, trailheads: &[Position]) -> usize {
+    // Every edge climbs one step, so searching from all trailheads at once visits the cells
+    // height by height and each cell's trail count is final before it is passed on
+    let mut trails: HashMap<Position, usize> = trailheads.iter().map(|&xy| (xy, 1)).collect();
+    let mut total = 0;
+
+    for xy in graph.bfs_from(trailheads.iter().copied()) {
+        let count = trails[&xy];
+        if values[xy] == SUMMIT_HEIGHT {
+            total += count;
+        }
+        for next in graph.neighbors(&xy).into_iter().flatten() {
+            *trails.entry(next).or_default() += count;
+        }
+    }
+    total
+}
+
+fn problem(values: &Grid<u8>, as_rating: bool) -> usize {
+    let graph = to_graph(values);
+    let trailheads: Vec<Position> = values
+        .iter_items()
+        .filter(|&(_, &height)| height == TRAILHEAD_HEIGHT)
+        .map(|(xy, _)| xy)
+        .collect();
+
+    if as_rating {
+        rating(values, &graph, &trailheads)
+    } else {
+        score(values, &graph, &trailheads)
+    }
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Grid<u8>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(problem(input, false))
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(problem(input, true))
+    }
+}
+
+#[test]
+fn test_part1() {
+    let input = r#"89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732
+"#;
+    assert_eq!(problem(&parse_input(input).unwrap(), false), 36);
+}
+
+#[test]
+fn test_part2() {
+    let input = r#"89010123
+78121874
+87430965
+96549874
+45678903
+32019012
+01329801
+10456732
+"#;
+    assert_eq!(problem(&parse_input(input).unwrap(), true), 81);
+}
@@ -0,0 +1,101 @@
+use crate::error::parse_token;
+use crate::{digits, AocError, Solution};
+use itertools::Itertools;
+use std::collections::HashMap;
+
+fn parse_input(input: &str) -> Result<Vec<u64>, AocError> {
+    input
+        .lines()
+        .enumerate()
+        .flat_map(|(i, line)| {
+            line.split_whitespace()
+                .map(move |s| parse_token(i, line, s))
+        })
+        .collect()
+}
+
+fn apply_rule(rock: u64) -> Vec<u64> {
+    match rock {
+        0 => vec![1],
+        r if digits(r).is_multiple_of(2) => {
+            let half = 10u64.pow(digits(r) / 2);
+            vec![r / half, r % half]
+        }
+        r => vec![r * 2024],
+    }
+}
+
+fn problem(rocks: &[u64], iterations: usize) -> usize {
+    let mut rocks = rocks.iter().copied().counts();
+
+    for _ in 0..iterations {
+        rocks = rocks
+            .into_iter()
+            .flat_map(|(rock, count)| {
+                apply_rule(rock)
+                    .into_iter()
+                    .map(move |new_rock| (new_rock, count))
+            })
+            .fold(HashMap::new(), |mut acc, (rock, count)| {
+                *acc.entry(rock).or_default() += count;
+                acc
+            });
+    }
+
+    rocks.values().sum()
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Vec<u64>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(problem(input, 25))
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(problem(input, 75))
+    }
+}
+
+#[test]
+fn test_1() {
+    assert_eq!(problem(&parse_input("125 17").unwrap(), 1), 3);
+}
+
+#[test]
+fn test_2() {
+    assert_eq!(problem(&parse_input("125 17").unwrap(), 2), 4);
+}
+
+#[test]
+fn test_3() {
+    assert_eq!(problem(&parse_input("125 17").unwrap(), 3), 5);
+}
+
+#[test]
+fn test_4() {
+    assert_eq!(problem(&parse_input("125 17").unwrap(), 4), 9);
+}
+
+#[test]
+fn test_5() {
+    assert_eq!(problem(&parse_input("125 17").unwrap(), 5), 13);
+}
+
+#[test]
+fn test_6() {
+    assert_eq!(problem(&parse_input("125 17").unwrap(), 6), 22);
+}
+
+#[test]
+fn test_25() {
+    assert_eq!(problem(&parse_input("125 17").unwrap(), 25), 55312);
+}
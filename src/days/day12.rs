@@ -0,0 +1,132 @@
+use crate::{AocError, Grid, RegionStats, Solution};
+
+fn calc_perimeter1(region: &RegionStats) -> usize {
+    region.perimeter
+}
+
+fn calc_perimeter2(region: &RegionStats) -> usize {
+    region.sides
+}
+
+fn problem(plants: &Grid<char>, calc_perimeter: fn(&RegionStats) -> usize) -> usize {
+    // Label each region, calculate cost for each, and add them up
+    plants
+        .label_regions(|a, b| a == b)
+        .stats
+        .iter()
+        .map(|region| region.area * calc_perimeter(region))
+        .sum()
+}
+
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Grid<char>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Grid::parse_str(input, Ok, '.')
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(problem(input, calc_perimeter1))
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(problem(input, calc_perimeter2))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn plants(input: &str) -> Grid<char> {
+        Grid::parse_str(input, Ok, '.').unwrap()
+    }
+
+    #[test]
+    fn test_part1_pattern1() {
+        let input = ["AAAA", "BBCD", "BBCC", "EEEC"].join("\n");
+        assert_eq!(problem(&plants(&input), calc_perimeter1), 140);
+    }
+
+    #[test]
+    fn test_part1_pattern2() {
+        let input = ["OOOOO", "OXOXO", "OOOOO", "OXOXO", "OOOOO"].join("\n");
+        assert_eq!(problem(&plants(&input), calc_perimeter1), 772);
+    }
+
+    #[test]
+    fn test_part1_pattern3() {
+        let input = [
+            "RRRRIICCFF",
+            "RRRRIICCCF",
+            "VVRRRCCFFF",
+            "VVRCCCJFFF",
+            "VVVVCJJCFE",
+            "VVIVCCJJEE",
+            "VVIIICJJEE",
+            "MIIIIIJJEE",
+            "MIIISIJEEE",
+            "MMMISSJEEE",
+        ]
+        .join("\n");
+        assert_eq!(problem(&plants(&input), calc_perimeter1), 1930);
+    }
+
+    #[test]
+    fn test_part2_pattern1() {
+        let input = ["AAAA", "BBCD", "BBCC", "EEEC"].join("\n");
+        assert_eq!(problem(&plants(&input), calc_perimeter2), 80);
+    }
+
+    #[test]
+    fn test_part2_pattern2() {
+        let input = ["OOOOO", "OXOXO", "OOOOO", "OXOXO", "OOOOO"].join("\n");
+        assert_eq!(problem(&plants(&input), calc_perimeter2), 436);
+    }
+
+    #[test]
+    fn test_part2_pattern3() {
+        let input = ["EEEEE", "EXXXX", "EEEEE", "EXXXX", "EEEEE"].join("\n");
+        assert_eq!(problem(&plants(&input), calc_perimeter2), 236);
+    }
+
+    #[test]
+    fn test_part2_pattern4() {
+        let input = ["AAAAAA", "AAABBA", "AAABBA", "ABBAAA", "ABBAAA", "AAAAAA"].join("\n");
+        assert_eq!(problem(&plants(&input), calc_perimeter2), 368);
+    }
+
+    #[test]
+    fn test_part2_pattern5() {
+        let input = [
+            "RRRRIICCFF",
+            "RRRRIICCCF",
+            "VVRRRCCFFF",
+            "VVRCCCJFFF",
+            "VVVVCJJCFE",
+            "VVIVCCJJEE",
+            "VVIIICJJEE",
+            "MIIIIIJJEE",
+            "MIIISIJEEE",
+            "MMMISSJEEE",
+        ]
+        .join("\n");
+        assert_eq!(problem(&plants(&input), calc_perimeter2), 1206);
+    }
+
+    #[test]
+    fn test_ragged_input() {
+        assert!(matches!(
+            Day12::parse("AAAA\nBBC\nBBCC"),
+            Err(AocError::Parse {
+                line: 2,
+                column: 4,
+                ..
+            })
+        ));
+    }
+}
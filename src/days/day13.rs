@@ -0,0 +1,176 @@
+use crate::error::parse_token;
+use crate::{AocError, Solution};
+
+const CALIBRATION_VALUE: f64 = 10000000000000.0;
+
+#[derive(Debug)]
+pub struct Problem {
+    button_a: (f64, f64),
+    button_b: (f64, f64),
+    prize: (f64, f64),
+}
+
+fn parse_numbers(line_idx: usize, line: &str) -> Result<(u32, u32), AocError> {
+    match line.split(['+', '=', ',']).collect::<Vec<_>>()[..] {
+        [_, x, _, y] => Ok((
+            parse_token(line_idx, line, x.trim())?,
+            parse_token(line_idx, line, y.trim())?,
+        )),
+        _ => Err(AocError::parse(
+            line_idx + 1,
+            1,
+            "Expected a line like `Button A: X+94, Y+34`",
+        )),
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<Problem>, AocError> {
+    let lines: Vec<(usize, &str)> = input
+        .lines()
+        .enumerate()
+        .filter(|(_, line)| !line.trim().is_empty())
+        .collect();
+
+    lines
+        .chunks(3)
+        .map(|game| {
+            let &[a, b, prize] = game else {
+                return Err(AocError::parse(
+                    game[0].0 + 1,
+                    1,
+                    "Expected two buttons and a prize",
+                ));
+            };
+            let [a, b, prize] = [a, b, prize].map(|(i, line)| parse_numbers(i, line));
+            let (a, b, prize) = (a?, b?, prize?);
+
+            Ok(Problem {
+                button_a: (a.0 as f64, a.1 as f64),
+                button_b: (b.0 as f64, b.1 as f64),
+                prize: (prize.0 as f64, prize.1 as f64),
+            })
+        })
+        .collect()
+}
+
+fn is_integer(x: f64) -> bool {
+    (x.round() - x).abs() < 1e-16
+}
+
+fn solve_problem(problem: &Problem) -> Option<(u64, u64)> {
+    let a = problem.button_a.0;
+    let b = problem.button_b.0;
+    let c = problem.button_a.1;
+    let d = problem.button_b.1;
+    let det = a * d - b * c;
+    let price = (problem.prize.0, problem.prize.1);
+
+    if det == 0.0 {
+        return None;
+    }
+
+    let a_presses = (d * price.0 - b * price.1) / det;
+    let b_presses = (-c * price.0 + a * price.1) / det;
+
+    if !is_integer(a_presses) || !is_integer(b_presses) {
+        return None;
+    }
+
+    Some((a_presses as u64, b_presses as u64))
+}
+
+fn solver(problems: &[Problem], calibrate: bool) -> u64 {
+    let calibration = if calibrate { CALIBRATION_VALUE } else { 0.0 };
+
+    problems
+        .iter()
+        .map(|problem| Problem {
+            prize: (problem.prize.0 + calibration, problem.prize.1 + calibration),
+            ..*problem
+        })
+        .filter_map(|problem| solve_problem(&problem))
+        .map(|(x, y)| 3 * x + y)
+        .sum()
+}
+
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Vec<Problem>;
+    type Answer1 = u64;
+    type Answer2 = u64;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<u64, AocError> {
+        Ok(solver(input, false))
+    }
+
+    fn part2(input: &Self::Input) -> Result<u64, AocError> {
+        Ok(solver(input, true))
+    }
+}
+
+#[test]
+fn test_part1() {
+    let input = r#"Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279"#;
+
+    assert_eq!(solver(&parse_input(input).unwrap(), false), 480);
+}
+
+#[test]
+fn test_part2() {
+    let input = r#"Button A: X+94, Y+34
+Button B: X+22, Y+67
+Prize: X=8400, Y=5400
+
+Button A: X+26, Y+66
+Button B: X+67, Y+21
+Prize: X=12748, Y=12176
+
+Button A: X+17, Y+86
+Button B: X+84, Y+37
+Prize: X=7870, Y=6450
+
+Button A: X+69, Y+23
+Button B: X+27, Y+71
+Prize: X=18641, Y=10279"#;
+
+    // AoC doesn't give answer for this...
+    assert_eq!(solver(&parse_input(input).unwrap(), true), 875318608908);
+}
+
+#[test]
+fn test_invalid_input() {
+    let input =
+        "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=5400\n\nButton A: X+26";
+    assert_eq!(
+        parse_input(input).unwrap_err(),
+        AocError::parse(5, 1, "Expected two buttons and a prize")
+    );
+    let input = "Button A: X+94, Y+34\nButton B: X+22, Y+67\nPrize: X=8400, Y=-5";
+    assert!(matches!(
+        parse_input(input),
+        Err(AocError::Parse {
+            line: 3,
+            column: 18,
+            ..
+        })
+    ));
+}
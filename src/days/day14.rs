@@ -0,0 +1,185 @@
+use crate::error::parse_token;
+use crate::{AocError, Solution, WrappingGrid};
+use itertools::Itertools;
+
+const WIDTH: usize = 101;
+const HEIGHT: usize = 103;
+const STEPS: usize = 100;
+
+#[derive(Clone, Debug)]
+pub struct Robot {
+    x: usize,
+    y: usize,
+    dx: i32,
+    dy: i32,
+}
+
+impl Robot {
+    fn step<T>(&mut self, board: &WrappingGrid<T>) {
+        (self.x, self.y) = board.wrap(
+            self.x as i64 + self.dx as i64,
+            self.y as i64 + self.dy as i64,
+        );
+    }
+}
+
+fn parse_line(line_idx: usize, line: &str) -> Result<Robot, AocError> {
+    match line.split([' ', '=', ',']).collect::<Vec<_>>()[..] {
+        ["p", x, y, "v", dx, dy] => Ok(Robot {
+            x: parse_token(line_idx, line, x)?,
+            y: parse_token(line_idx, line, y)?,
+            dx: parse_token(line_idx, line, dx)?,
+            dy: parse_token(line_idx, line, dy)?,
+        }),
+        _ => Err(AocError::parse(
+            line_idx + 1,
+            1,
+            "Expected a robot like `p=0,4 v=3,-3`",
+        )),
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<Robot>, AocError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| parse_line(i, line.trim_end()))
+        .collect()
+}
+
+fn problem(robots: &[Robot], width: usize, height: usize, steps: usize) -> usize {
+    let mut robots = robots.to_vec();
+    let board = WrappingGrid::new(width, height, ());
+
+    for _ in 0..steps {
+        for robot in robots.iter_mut() {
+            robot.step(&board);
+        }
+    }
+
+    compute_safety_factor(&robots, width, height)
+}
+
+fn compute_safety_factor(robots: &[Robot], width: usize, height: usize) -> usize {
+    let mid_x = width / 2;
+    let mid_y = height / 2;
+    robots
+        .iter()
+        .filter_map(|robot| match (robot.x, robot.y) {
+            (x, y) if x < mid_x && y < mid_y => Some(0),
+            (x, y) if x > mid_x && y < mid_y => Some(1),
+            (x, y) if x < mid_x && y > mid_y => Some(2),
+            (x, y) if x > mid_x && y > mid_y => Some(3),
+            _ => None,
+        })
+        .counts()
+        .into_values()
+        .product()
+}
+
+/// The first step at which the robots draw a picture. Positions repeat every `width * height`
+/// steps, so only that many need checking. Most steps scatter the robots evenly, which makes
+/// the product of the quadrant counts large, while the picture clusters most of them in one
+/// place, so it is taken to be the step with the lowest safety factor.
+fn find_easter_egg(robots: &[Robot], width: usize, height: usize) -> usize {
+    let mut robots = robots.to_vec();
+    let board = WrappingGrid::new(width, height, ());
+    let mut best = (compute_safety_factor(&robots, width, height), 0);
+
+    for step in 1..width * height {
+        for robot in robots.iter_mut() {
+            robot.step(&board);
+        }
+        best = best.min((compute_safety_factor(&robots, width, height), step));
+    }
+
+    best.1
+}
+
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Vec<Robot>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(problem(input, WIDTH, HEIGHT, STEPS))
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(find_easter_egg(input, WIDTH, HEIGHT))
+    }
+}
+
+#[test]
+fn test_part1() {
+    let input = r#"p=0,4 v=3,-3
+p=6,3 v=-1,-3
+p=10,3 v=-1,2
+p=2,0 v=2,-1
+p=0,0 v=1,3
+p=3,0 v=-2,-2
+p=7,6 v=-1,-3
+p=3,0 v=-1,-2
+p=9,3 v=2,3
+p=7,3 v=-1,2
+p=2,4 v=2,-3
+p=9,5 v=-3,-3"#;
+    assert_eq!(problem(&parse_input(input).unwrap(), 11, 7, 100), 12);
+}
+
+#[test]
+fn test_find_easter_egg() {
+    // A cluster of robots that all meet in the top left quadrant at step 1234, among others
+    // scattered at random
+    const STEP: i64 = 1234;
+    let mut seed = 0x2024_1214_u64;
+    let mut random = |n: i64| {
+        seed = seed
+            .wrapping_mul(6364136223846793005)
+            .wrapping_add(1442695040888963407);
+        (seed >> 33) as i64 % n
+    };
+    let (width, height) = (WIDTH as i64, HEIGHT as i64);
+
+    let robots: Vec<Robot> = (0..200)
+        .map(|i| {
+            let (dx, dy) = (random(201) - 100, random(205) - 102);
+            let (x, y) = if i < 100 {
+                let (x, y) = (10 + random(30), 10 + random(30));
+                (x - STEP * dx, y - STEP * dy)
+            } else {
+                (random(width), random(height))
+            };
+            Robot {
+                x: x.rem_euclid(width) as usize,
+                y: y.rem_euclid(height) as usize,
+                dx: dx as i32,
+                dy: dy as i32,
+            }
+        })
+        .collect();
+
+    assert_eq!(find_easter_egg(&robots, WIDTH, HEIGHT), STEP as usize);
+}
+
+#[test]
+fn test_invalid_input() {
+    assert!(matches!(
+        parse_input("p=0,4 v=3,-3\np=-1,4 v=3,-3"),
+        Err(AocError::Parse {
+            line: 2,
+            column: 3,
+            ..
+        })
+    ));
+    assert_eq!(
+        parse_input("p=0,4 v=3").unwrap_err(),
+        AocError::parse(1, 1, "Expected a robot like `p=0,4 v=3,-3`")
+    );
+}
@@ -1,8 +1,7 @@
-use advent_2024::Grid;
-use std::{error::Error, fs};
+use crate::{AocError, Direction, Grid, Solution};
 
 #[derive(Default, Copy, Clone, PartialEq, Eq)]
-enum Cell {
+pub enum Cell {
     #[default]
     Empty,
     Robot,
@@ -13,7 +12,7 @@ enum Cell {
 }
 
 impl Cell {
-    fn to_char(&self) -> char {
+    fn to_char(self) -> char {
         match self {
             Cell::Wall => '#',
             Cell::Robot => '@',
@@ -53,102 +52,57 @@ impl std::fmt::Debug for Cell {
     }
 }
 
-#[derive(Copy, Clone, PartialEq, Eq)]
-enum Direction {
-    Left,
-    Right,
-    Up,
-    Down,
-}
-
-impl Direction {
-    fn from_char(c: char) -> Option<Direction> {
-        match c {
-            '>' => Some(Direction::Right),
-            '<' => Some(Direction::Left),
-            '^' => Some(Direction::Up),
-            'v' => Some(Direction::Down),
-            _ => None,
-        }
-    }
-
-    fn to_char(&self) -> char {
-        match self {
-            Direction::Right => '>',
-            Direction::Left => '<',
-            Direction::Up => '^',
-            Direction::Down => 'v',
-        }
-    }
-    fn offset(&self) -> (i32, i32) {
-        match self {
-            Direction::Up => (0, -1),
-            Direction::Down => (0, 1),
-            Direction::Left => (-1, 0),
-            Direction::Right => (1, 0),
-        }
-    }
-}
-
-impl std::fmt::Display for Direction {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        write!(f, "{}", self.to_char())
-    }
-}
-
-impl std::fmt::Debug for Direction {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        std::fmt::Display::fmt(self, f)
-    }
+fn step(
+    grid: &Grid<Cell>,
+    pos: (usize, usize),
+    dir: Direction,
+) -> Result<(usize, usize), AocError> {
+    grid.step(pos, dir)
+        .ok_or_else(|| AocError::unsolvable("Moved off the edge of the room"))
 }
 
-type ParserOutput = Result<(Grid<Cell>, Vec<Direction>), Box<dyn Error>>;
-
-fn parse_input(input: &str, widen: bool) -> ParserOutput {
-    let (raw_room_str, commands_str) = input.split_once("\n\n").unwrap_or((&input, ""));
+type ParserOutput = Result<(Grid<Cell>, Vec<Direction>), AocError>;
 
-    fn widen_room(room_str: &str) -> String {
-        room_str
-            .chars()
-            .flat_map(|c| match c {
-                '#' => vec!['#', '#'],
-                'O' => vec!['[', ']'],
-                '.' => vec!['.', '.'],
-                '@' => vec!['@', '.'],
-                c => vec![c],
-            })
-            .collect()
-    }
+fn parse_input(input: &str) -> ParserOutput {
+    let (room_str, commands_str) = input.split_once("\n\n").unwrap_or((input, ""));
 
-    let room_str = if widen {
-        widen_room(raw_room_str)
-    } else {
-        raw_room_str.to_string()
-    };
-    let grid: Grid<Cell> = Grid::parse_str(&room_str, Cell::try_from, Cell::default())?;
+    let grid: Grid<Cell> = Grid::parse_str(room_str, Cell::try_from, Cell::default())?;
 
+    // The moves start after the room and the blank line, and may wrap over several lines
+    let offset = room_str.lines().count() + 1;
     let commands: Vec<Direction> = commands_str
-        .chars()
-        .filter_map(Direction::from_char)
-        .collect();
+        .lines()
+        .enumerate()
+        .flat_map(|(i, line)| {
+            line.trim_end().chars().enumerate().map(move |(x, c)| {
+                Direction::from_char(c)
+                    .filter(|direction| direction.is_cardinal())
+                    .ok_or_else(|| {
+                        AocError::parse(offset + i + 1, x + 1, format!("Invalid move {c:?}"))
+                    })
+            })
+        })
+        .collect::<Result<_, _>>()?;
 
     Ok((grid, commands))
 }
 
+fn widen(grid: &Grid<Cell>) -> Grid<Cell> {
+    grid.scale_x(2, |cell| match cell {
+        Cell::Box => [Cell::WideBoxLeft, Cell::WideBoxRight],
+        Cell::Robot => [Cell::Robot, Cell::Empty],
+        &cell => [cell, cell],
+    })
+}
+
 fn check_wide_vertical_move(
     grid: &Grid<Cell>,
     left: (usize, usize),
     right: (usize, usize),
     dir: Direction,
-) -> Result<bool, Box<dyn Error>> {
-    let next_left = (
-        (left.0 as i32 + dir.offset().0) as usize,
-        (left.1 as i32 + dir.offset().1) as usize,
-    );
-    let next_right = (
-        (right.0 as i32 + dir.offset().0) as usize,
-        (right.1 as i32 + dir.offset().1) as usize,
-    );
+) -> Result<bool, AocError> {
+    let next_left = step(grid, left, dir)?;
+    let next_right = step(grid, right, dir)?;
 
     match (grid[next_left], grid[next_right]) {
         (Cell::Empty, Cell::Empty) => Ok(true),
@@ -161,19 +115,19 @@ fn check_wide_box_move(
     grid: &Grid<Cell>,
     pos: (usize, usize),
     dir: Direction,
-) -> Result<bool, Box<dyn Error>> {
+) -> Result<bool, AocError> {
     match (grid[pos], dir) {
         (Cell::WideBoxLeft, Direction::Up | Direction::Down) => {
-            let right_pos = (pos.0 + 1, pos.1);
+            let right_pos = step(grid, pos, Direction::Right)?;
             if grid[right_pos] != Cell::WideBoxRight {
-                return Err("Invalid wide box state".into());
+                return Err(AocError::unsolvable("Invalid wide box state"));
             }
             check_wide_vertical_move(grid, pos, right_pos, dir)
         }
         (Cell::WideBoxRight, Direction::Up | Direction::Down) => {
-            let left_pos = (pos.0 - 1, pos.1);
+            let left_pos = step(grid, pos, Direction::Left)?;
             if grid[left_pos] != Cell::WideBoxLeft {
-                return Err("Invalid wide box state".into());
+                return Err(AocError::unsolvable("Invalid wide box state"));
             }
             check_wide_vertical_move(grid, left_pos, pos, dir)
         }
@@ -181,22 +135,17 @@ fn check_wide_box_move(
     }
 }
 
-fn can_move(
-    grid: &Grid<Cell>,
-    pos: (usize, usize),
-    dir: Direction,
-) -> Result<bool, Box<dyn Error>> {
-    let next_pos = (
-        (pos.0 as i32 + dir.offset().0) as usize,
-        (pos.1 as i32 + dir.offset().1) as usize,
-    );
+fn can_move(grid: &Grid<Cell>, pos: (usize, usize), dir: Direction) -> Result<bool, AocError> {
+    let next_pos = step(grid, pos, dir)?;
 
     match grid[next_pos] {
         Cell::Empty => Ok(true),
         Cell::Wall => Ok(false),
         Cell::Box => can_move(grid, next_pos, dir),
         Cell::WideBoxLeft | Cell::WideBoxRight => check_wide_box_move(grid, next_pos, dir),
-        Cell::Robot => Err("Invalid state: encountered another robot".into()),
+        Cell::Robot => Err(AocError::unsolvable(
+            "Invalid state: encountered another robot",
+        )),
     }
 }
 
@@ -204,15 +153,15 @@ fn handle_box_push(
     grid: &mut Grid<Cell>,
     pos: (usize, usize),
     dir: Direction,
-) -> Result<(), Box<dyn Error>> {
+) -> Result<(), AocError> {
     match (grid[pos], dir) {
         (Cell::WideBoxLeft, Direction::Up | Direction::Down) => {
-            let right_pos = (pos.0 + 1, pos.1);
+            let right_pos = step(grid, pos, Direction::Right)?;
             push(grid, pos, dir)?;
             push(grid, right_pos, dir)?;
         }
         (Cell::WideBoxRight, Direction::Up | Direction::Down) => {
-            let left_pos = (pos.0 - 1, pos.1);
+            let left_pos = step(grid, pos, Direction::Left)?;
             push(grid, left_pos, dir)?;
             push(grid, pos, dir)?;
         }
@@ -221,15 +170,12 @@ fn handle_box_push(
     Ok(())
 }
 
-fn push(grid: &mut Grid<Cell>, pos: (usize, usize), dir: Direction) -> Result<(), Box<dyn Error>> {
+fn push(grid: &mut Grid<Cell>, pos: (usize, usize), dir: Direction) -> Result<(), AocError> {
     if !can_move(grid, pos, dir)? {
         return Ok(());
     }
 
-    let next_pos = (
-        (pos.0 as i32 + dir.offset().0) as usize,
-        (pos.1 as i32 + dir.offset().1) as usize,
-    );
+    let next_pos = step(grid, pos, dir)?;
 
     match grid[next_pos] {
         Cell::Empty => {
@@ -244,12 +190,12 @@ fn push(grid: &mut Grid<Cell>, pos: (usize, usize), dir: Direction) -> Result<()
     Ok(())
 }
 
-fn find_robot(grid: &Grid<Cell>) -> Result<(usize, usize), Box<dyn Error>> {
+fn find_robot(grid: &Grid<Cell>) -> Result<(usize, usize), AocError> {
     grid.iter()
         .enumerate()
         .find(|(_, &cell)| cell == Cell::Robot)
         .map(|(idx, _)| grid.idx_to_xy(idx))
-        .ok_or_else(|| "No robot found".into())
+        .ok_or(AocError::Missing("robot"))
 }
 
 fn compute_gps(grid: &Grid<Cell>) -> usize {
@@ -263,22 +209,35 @@ fn compute_gps(grid: &Grid<Cell>) -> usize {
         .sum()
 }
 
-fn solver(input: &str, parser: fn(&str) -> ParserOutput) -> Result<usize, Box<dyn Error>> {
-    let (mut grid, commands) = parser(&input)?;
-    for command in commands {
+fn solver(mut grid: Grid<Cell>, commands: &[Direction]) -> Result<usize, AocError> {
+    for &command in commands {
         let robot_xy = find_robot(&grid)?;
-        let _ = push(&mut grid, robot_xy, command);
+        push(&mut grid, robot_xy, command)?;
     }
 
     Ok(compute_gps(&grid))
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let path: &str = "data/day15.input";
-    let input = fs::read_to_string(path)?;
-    println!("Part 1: {:?}", solver(&input, |x| parse_input(x, false))?);
-    println!("Part 2: {:?}", solver(&input, |x| parse_input(x, true))?);
-    Ok(())
+pub struct Day15;
+
+impl Solution for Day15 {
+    type Input = (Grid<Cell>, Vec<Direction>);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize, AocError> {
+        let (grid, commands) = input;
+        solver(grid.clone(), commands)
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize, AocError> {
+        let (grid, commands) = input;
+        solver(widen(grid), commands)
+    }
 }
 
 #[cfg(test)]
@@ -320,25 +279,40 @@ v^^>>><<^^<>>^v^<v^vv<>v^<<>^<^v^v><^<<<><<^<v><v<>vv>>v><v^<vv<>v^<<^"#;
 
     #[test]
     fn test_small_example() {
-        assert_eq!(
-            solver(&SMALL_EXAMPLE, |x| parse_input(x, false)).unwrap(),
-            2028
-        );
+        let input = Day15::parse(SMALL_EXAMPLE).unwrap();
+        assert_eq!(Day15::part1(&input).unwrap(), 2028);
     }
 
     #[test]
     fn test_large_example() {
-        assert_eq!(
-            solver(&LARGE_EXAMPLE, |x| parse_input(x, false)).unwrap(),
-            10092
-        );
+        let input = Day15::parse(LARGE_EXAMPLE).unwrap();
+        assert_eq!(Day15::part1(&input).unwrap(), 10092);
     }
 
     #[test]
     fn test_large_wide_example() {
+        let input = Day15::parse(LARGE_EXAMPLE).unwrap();
+        assert_eq!(Day15::part2(&input).unwrap(), 9021);
+    }
+
+    #[test]
+    fn test_invalid_moves() {
         assert_eq!(
-            solver(&LARGE_EXAMPLE, |x| parse_input(x, true)).unwrap(),
-            9021
+            parse_input("###\n#@#\n###\n\n<^\n>x").unwrap_err(),
+            AocError::parse(6, 2, "Invalid move 'x'")
         );
     }
+
+    #[test]
+    fn test_moving_off_the_room() {
+        // Without walls around the room, nothing stops the robot or a box at the edge
+        for input in ["..@\n\n>", "O@.\n\n<", "@\nO\n\nvv"] {
+            let (grid, commands) = parse_input(input).unwrap();
+            assert_eq!(
+                solver(grid, &commands),
+                Err(AocError::unsolvable("Moved off the edge of the room")),
+                "{input}"
+            );
+        }
+    }
 }
@@ -0,0 +1,253 @@
+use crate::graph::Dijkstra;
+use crate::grid::search::{astar_states, State};
+use crate::{AocError, Direction, Grid, Point, Solution};
+use itertools::Itertools;
+
+#[derive(Default, PartialEq, Eq, Copy, Clone, Hash, PartialOrd, Ord)]
+pub enum CellType {
+    #[default]
+    Empty,
+    Start,
+    End,
+    Wall,
+}
+
+impl CellType {
+    fn to_char(self) -> char {
+        match self {
+            CellType::Wall => '#',
+            CellType::Start => 'S',
+            CellType::End => 'O',
+            CellType::Empty => '.',
+        }
+    }
+}
+
+impl TryFrom<char> for CellType {
+    type Error = String;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '#' => Ok(CellType::Wall),
+            'S' => Ok(CellType::Start),
+            'E' => Ok(CellType::End),
+            '.' => Ok(CellType::Empty),
+            _ => Err(format!("Invalid character: {}", c)),
+        }
+    }
+}
+
+impl std::fmt::Display for CellType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+impl std::fmt::Debug for CellType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(self, f)
+    }
+}
+
+#[derive(Hash, PartialEq, Eq, PartialOrd, Ord, Copy, Clone)]
+struct Cell {
+    cell_type: CellType,
+    direction: Direction,
+    xy: (usize, usize),
+}
+
+impl std::fmt::Display for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "({}, {}, {:?})", self.cell_type, self.direction, self.xy)
+    }
+}
+
+impl std::fmt::Debug for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "({:?}, {:?}, {:?})",
+            self.cell_type, self.direction, self.xy
+        )
+    }
+}
+
+const ROTATION_COST: usize = 1000;
+const STEP_COST: usize = 1;
+const STARTING_DIRECTION: Direction = Direction::Right;
+
+/// Turning in place, or stepping forward onto anything but a wall.
+fn moves(grid: &Grid<CellType>, from: &Cell) -> Vec<(Cell, usize)> {
+    let turn = |direction| (Cell { direction, ..*from }, ROTATION_COST);
+    let mut moves = vec![
+        turn(from.direction.turn_right()),
+        turn(from.direction.turn_left()),
+    ];
+
+    if let Some(next_xy) = grid.step(from.xy, from.direction) {
+        if grid[next_xy] != CellType::Wall {
+            let next = Cell {
+                cell_type: grid[next_xy],
+                xy: next_xy,
+                ..*from
+            };
+            moves.push((next, STEP_COST));
+        }
+    }
+    moves
+}
+
+fn find_thing(
+    grid: &Grid<CellType>,
+    query: CellType,
+    what: &'static str,
+) -> Result<(usize, usize), AocError> {
+    grid.iter()
+        .enumerate()
+        .find(|(_, &cell_type)| cell_type == query)
+        .map(|(idx, _)| grid.idx_to_xy(idx))
+        .ok_or(AocError::Missing(what))
+}
+
+/// The search layer holding each facing.
+fn layer(direction: Direction) -> usize {
+    Direction::CARDINAL
+        .iter()
+        .position(|&d| d == direction)
+        .unwrap_or_default()
+}
+
+fn solver1(grid: &Grid<CellType>) -> Result<usize, AocError> {
+    // Search the states lazily, with one layer of the grid per facing
+    let successors = |state: State| {
+        let cell = Cell {
+            cell_type: grid[state.xy],
+            direction: Direction::CARDINAL[state.layer],
+            xy: state.xy,
+        };
+        moves(grid, &cell)
+            .into_iter()
+            .map(|(next, cost)| (State::new(next.xy, layer(next.direction)), cost))
+            .collect::<Vec<_>>()
+    };
+
+    // Find start
+    let start_xy = find_thing(grid, CellType::Start, "start")?;
+    let end_xy = find_thing(grid, CellType::End, "end")?;
+    let start = State::new(start_xy, layer(STARTING_DIRECTION));
+
+    // Every step costs at least STEP_COST, so this never overestimates
+    let heuristic = |state: State| Point::from(state.xy).manhattan(end_xy.into()) * STEP_COST;
+    let search = astar_states(
+        grid.width,
+        grid.height,
+        Direction::CARDINAL.len(),
+        [start],
+        successors,
+        |state| state.xy == end_xy,
+        heuristic,
+    );
+
+    search
+        .goal()
+        .and_then(|goal| search.distance(goal))
+        .ok_or_else(|| AocError::unsolvable("No path from start to end"))
+}
+
+fn solver2(grid: &Grid<CellType>) -> Result<usize, AocError> {
+    let successors = |cell: &Cell| moves(grid, cell);
+
+    // Find start
+    let start_xy = find_thing(grid, CellType::Start, "start")?;
+    let end_xy = find_thing(grid, CellType::End, "end")?;
+
+    let start = Cell {
+        cell_type: CellType::Start,
+        direction: STARTING_DIRECTION,
+        xy: start_xy,
+    };
+
+    let dag = Dijkstra::new(&successors, start)
+        .shortest_path_dag_where(|cell| cell.xy == end_xy)
+        .ok_or_else(|| AocError::unsolvable("No path from start to end"))?;
+
+    Ok(dag
+        .nodes_on_any_path()
+        .into_iter()
+        .map(|cell| cell.xy)
+        .unique()
+        .count())
+}
+
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = Grid<CellType>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Grid::parse_str(input, CellType::try_from, CellType::default())
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize, AocError> {
+        solver1(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize, AocError> {
+        solver2(input)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE1: &str = r#"###############
+#.......#....E#
+#.#.###.#.###.#
+#.....#.#...#.#
+#.###.#####.#.#
+#.#.#.......#.#
+#.#.#####.###.#
+#...........#.#
+###.#.#####.#.#
+#...#.....#.#.#
+#.#.#.###.#.#.#
+#.....#...#.#.#
+#.###.#.#.#.#.#
+#S..#.....#...#
+###############"#;
+
+    const EXAMPLE2: &str = r#"#################
+#...#...#...#..E#
+#.#.#.#.#.#.#.#.#
+#.#.#.#...#...#.#
+#.#.#.#.###.#.#.#
+#...#.#.#.....#.#
+#.#.#.#.#.#####.#
+#.#...#.#.#.....#
+#.#.#####.#.###.#
+#.#.#.......#...#
+#.#.###.#####.###
+#.#.#...#.....#.#
+#.#.#.#####.###.#
+#.#.#.........#.#
+#.#.#.#########.#
+#S#.............#
+#################"#;
+
+    #[test]
+    fn test_example1() {
+        let grid = Day16::parse(EXAMPLE1).unwrap();
+        assert_eq!(Day16::part1(&grid).unwrap(), 7036);
+        assert_eq!(Day16::part2(&grid).unwrap(), 45);
+    }
+
+    #[test]
+    fn test_example2() {
+        let grid = Day16::parse(EXAMPLE2).unwrap();
+        assert_eq!(Day16::part1(&grid).unwrap(), 11048);
+        assert_eq!(Day16::part2(&grid).unwrap(), 64);
+    }
+}
@@ -1,4 +1,6 @@
-use std::{error::Error, fs};
+use crate::error::parse_token;
+use crate::{AocError, Solution};
+use std::str::FromStr;
 
 const A: usize = 0;
 const B: usize = 1;
@@ -10,25 +12,25 @@ fn division(value: usize, combo: usize) -> usize {
     (value / divisor).floor() as usize
 }
 
-fn adv(device: &mut Device, operand: Command) -> Result<(), Box<dyn Error>> {
+fn adv(device: &mut Device, operand: Command) -> Result<(), AocError> {
     device.registers[A] = division(device.registers[A], operand.combo(device.registers)?);
     device.increment_ip();
     Ok(())
 }
 
-fn bxl(device: &mut Device, operand: Command) -> Result<(), Box<dyn Error>> {
-    device.registers[B] = device.registers[B] ^ operand.literal();
+fn bxl(device: &mut Device, operand: Command) -> Result<(), AocError> {
+    device.registers[B] ^= operand.literal();
     device.increment_ip();
     Ok(())
 }
 
-fn bst(device: &mut Device, operand: Command) -> Result<(), Box<dyn Error>> {
+fn bst(device: &mut Device, operand: Command) -> Result<(), AocError> {
     device.registers[B] = operand.combo(device.registers)? % 8;
     device.increment_ip();
     Ok(())
 }
 
-fn jnz(device: &mut Device, operand: Command) -> Result<(), Box<dyn Error>> {
+fn jnz(device: &mut Device, operand: Command) -> Result<(), AocError> {
     if device.registers[A] != 0 {
         device.ip = operand.literal();
         return Ok(());
@@ -37,25 +39,25 @@ fn jnz(device: &mut Device, operand: Command) -> Result<(), Box<dyn Error>> {
     Ok(())
 }
 
-fn bxc(device: &mut Device, _: Command) -> Result<(), Box<dyn Error>> {
-    device.registers[B] = device.registers[B] ^ device.registers[C];
+fn bxc(device: &mut Device, _: Command) -> Result<(), AocError> {
+    device.registers[B] ^= device.registers[C];
     device.increment_ip();
     Ok(())
 }
 
-fn out(device: &mut Device, operand: Command) -> Result<(), Box<dyn Error>> {
+fn out(device: &mut Device, operand: Command) -> Result<(), AocError> {
     device.output.push(operand.combo(device.registers)? % 8);
     device.increment_ip();
     Ok(())
 }
 
-fn bdv(device: &mut Device, operand: Command) -> Result<(), Box<dyn Error>> {
+fn bdv(device: &mut Device, operand: Command) -> Result<(), AocError> {
     device.registers[B] = division(device.registers[A], operand.combo(device.registers)?);
     device.increment_ip();
     Ok(())
 }
 
-fn cdv(device: &mut Device, operand: Command) -> Result<(), Box<dyn Error>> {
+fn cdv(device: &mut Device, operand: Command) -> Result<(), AocError> {
     device.registers[C] = division(device.registers[A], operand.combo(device.registers)?);
     device.increment_ip();
     Ok(())
@@ -78,7 +80,7 @@ impl Command {
         self as usize
     }
 
-    fn combo(&self, registers: [usize; 3]) -> Result<usize, Box<dyn Error>> {
+    fn combo(&self, registers: [usize; 3]) -> Result<usize, AocError> {
         match self {
             Command::Adv => Ok(0),
             Command::Bxl => Ok(1),
@@ -87,7 +89,7 @@ impl Command {
             Command::Bxc => Ok(registers[A]),
             Command::Out => Ok(registers[B]),
             Command::Bdv => Ok(registers[C]),
-            Command::Cdv => Err("Invalid command for combo".into()),
+            Command::Cdv => Err(AocError::unsolvable("Invalid command for combo")),
         }
     }
 }
@@ -110,8 +112,17 @@ impl TryFrom<u8> for Command {
     }
 }
 
-#[derive(Debug)]
-struct Device {
+impl FromStr for Command {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let opcode: u8 = s.parse().map_err(|e| format!("{e}"))?;
+        Command::try_from(opcode)
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct Device {
     registers: [usize; 3],
     ip: usize,
     output: Vec<usize>,
@@ -119,7 +130,7 @@ struct Device {
 }
 
 impl Device {
-    fn from_program(input: &str) -> Result<Self, Box<dyn Error>> {
+    fn from_program(input: &str) -> Result<Self, AocError> {
         let (registers, commands) = parse_input(input)?;
         Ok(Device {
             registers,
@@ -129,7 +140,7 @@ impl Device {
         })
     }
 
-    fn apply(&mut self, opcode: Command, operand: Command) -> Result<(), Box<dyn Error>> {
+    fn apply(&mut self, opcode: Command, operand: Command) -> Result<(), AocError> {
         match opcode {
             Command::Adv => adv(self, operand),
             Command::Bxl => bxl(self, operand),
@@ -142,8 +153,9 @@ impl Device {
         }
     }
 
+    /// Halts once there is no full instruction left at `ip`, e.g. after a jump to the last value.
     fn is_halted(&self) -> bool {
-        self.ip + 1 > self.commands.len()
+        self.ip + 1 >= self.commands.len()
     }
 
     fn next_commands(&self) -> (Command, Command) {
@@ -154,7 +166,7 @@ impl Device {
         self.ip += 2;
     }
 
-    fn execute(&mut self) -> Result<(), Box<dyn std::error::Error>> {
+    fn execute(&mut self) -> Result<(), AocError> {
         while !self.is_halted() {
             let (opcode, operand) = self.next_commands();
             self.apply(opcode, operand)?;
@@ -163,36 +175,55 @@ impl Device {
     }
 }
 
-fn parse_input(input: &str) -> Result<([usize; 3], Vec<Command>), Box<dyn Error>> {
-    let (registers_part, program_part) = input.split_once("\n\n").ok_or("uh oh")?;
+fn parse_input(input: &str) -> Result<([usize; 3], Vec<Command>), AocError> {
+    let (registers_part, program_part) = input
+        .split_once("\n\n")
+        .ok_or(AocError::Missing("program"))?;
     let register_values: Vec<usize> = registers_part
         .lines()
-        .filter_map(|line| line.split(":").nth(1)?.trim().parse().ok())
-        .collect();
+        .enumerate()
+        .map(|(idx, line)| {
+            let (_, value) = line
+                .split_once(':')
+                .ok_or_else(|| AocError::parse(idx + 1, 1, "Expected `Register X: value`"))?;
+            parse_token(idx, line, value.trim())
+        })
+        .collect::<Result<_, _>>()?;
 
     let registers = [
-        *register_values.get(0).ok_or("Missing register A")?,
-        *register_values.get(1).ok_or("Missing register B")?,
-        *register_values.get(2).ok_or("Missing register C")?,
+        *register_values
+            .first()
+            .ok_or(AocError::Missing("register A"))?,
+        *register_values
+            .get(1)
+            .ok_or(AocError::Missing("register B"))?,
+        *register_values
+            .get(2)
+            .ok_or(AocError::Missing("register C"))?,
     ];
 
-    let commands: Vec<Command> = program_part
-        .split(':')
-        .nth(1)
-        .ok_or("Invalid line of program commands")?
+    let program_idx = registers_part.lines().count() + 1;
+    let program_line = program_part.trim_end();
+    let (_, program) = program_line
+        .split_once(':')
+        .ok_or_else(|| AocError::parse(program_idx + 1, 1, "Expected `Program: ...`"))?;
+    let commands = program
         .split(',')
-        .map(str::trim)
-        .filter_map(|x| x.parse::<u8>().ok())
-        .map(Command::try_from)
-        .collect::<Vec<_>>()
-        .into_iter()
+        .map(|token| parse_token(program_idx, program_line, token.trim()))
         .collect::<Result<Vec<_>, _>>()?;
+    if commands.len() % 2 != 0 {
+        return Err(AocError::parse(
+            program_idx + 1,
+            program_line.len() + 1,
+            "Expected an operand after the last opcode",
+        ));
+    }
 
     Ok((registers, commands))
 }
 
-fn simulator(input: &str) -> Result<String, Box<dyn Error>> {
-    let mut device = Device::from_program(input)?;
+fn simulator(device: &Device) -> Result<String, AocError> {
+    let mut device = device.clone();
 
     device.execute()?;
 
@@ -204,7 +235,7 @@ fn simulator(input: &str) -> Result<String, Box<dyn Error>> {
         .join(","))
 }
 
-fn run_program(initial_value: usize, program: &[Command]) -> Result<Vec<Command>, Box<dyn Error>> {
+fn run_program(initial_value: usize, program: &[Command]) -> Result<Vec<Command>, AocError> {
     let mut device = Device {
         registers: [initial_value, 0, 0],
         ip: 0,
@@ -248,20 +279,35 @@ fn find_quine_value(program: &[Command], position: usize, current_a: usize) -> O
 
 // Stolen from Reddit user /u/mental-chaos
 // https://www.reddit.com/r/adventofcode/comments/1hg38ah/2024_day_17_solutions/m2gge90/
-fn find_quine(input: &str) -> Result<usize, Box<dyn Error>> {
-    let device = Device::from_program(input)?;
-    let program = device.commands;
-
-    find_quine_value(&program, program.len() - 1, 0)
-        .ok_or_else(|| Box::<dyn Error>::from("No solution found"))
+fn find_quine(device: &Device) -> Result<usize, AocError> {
+    let program = &device.commands;
+    let last = program
+        .len()
+        .checked_sub(1)
+        .ok_or(AocError::Missing("program"))?;
+
+    find_quine_value(program, last, 0)
+        .ok_or_else(|| AocError::unsolvable("No register A value makes the program output itself"))
 }
 
-fn main() -> Result<(), Box<dyn Error>> {
-    let path: &str = "data/day17.input";
-    let input = fs::read_to_string(path)?;
-    println!("Part 1: {:?}", simulator(&input)?);
-    println!("Part 2: {:?}", find_quine(&input)?);
-    Ok(())
+pub struct Day17;
+
+impl Solution for Day17 {
+    type Input = Device;
+    type Answer1 = String;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Device::from_program(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<String, AocError> {
+        simulator(input)
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize, AocError> {
+        find_quine(input)
+    }
 }
 
 #[cfg(test)]
@@ -282,11 +328,30 @@ Program: 0,3,5,4,3,0"#;
 
     #[test]
     fn test_example() {
-        assert_eq!(simulator(&EXAMPLE).unwrap(), "4,6,3,5,6,3,5,2,1,0");
+        let device = Device::from_program(EXAMPLE).unwrap();
+        assert_eq!(simulator(&device).unwrap(), "4,6,3,5,6,3,5,2,1,0");
     }
 
     #[test]
     fn test_example2() {
-        assert_eq!(find_quine(&EXAMPLE2).unwrap(), 117440);
+        let device = Device::from_program(EXAMPLE2).unwrap();
+        assert_eq!(find_quine(&device).unwrap(), 117440);
+    }
+
+    #[test]
+    fn test_invalid_programs() {
+        let registers = "Register A: 1\nRegister B: 0\nRegister C: 0\n\n";
+        assert_eq!(
+            Device::from_program(&format!("{registers}Program: 0,1,5")).unwrap_err(),
+            AocError::parse(5, 15, "Expected an operand after the last opcode")
+        );
+        assert!(matches!(
+            Device::from_program(&format!("{registers}Program: ")),
+            Err(AocError::Parse { line: 5, .. })
+        ));
+
+        // Jumping to the last value leaves no operand to read, so the program halts there
+        let device = Device::from_program(&format!("{registers}Program: 3,3,5,4")).unwrap();
+        assert_eq!(simulator(&device).unwrap(), "");
     }
 }
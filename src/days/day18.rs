@@ -0,0 +1,233 @@
+use crate::error::parse_token;
+use crate::grid::search::{astar, State};
+use crate::{AocError, Graph, Grid, Point, Solution};
+use itertools::iproduct;
+use std::collections::HashSet;
+
+const WIDTH: usize = 71;
+const HEIGHT: usize = 71;
+const NBYTES: usize = 1024;
+
+#[derive(Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+enum Cell {
+    #[default]
+    Empty,
+    Corrupted,
+    Start,
+    End,
+}
+
+impl Cell {
+    fn to_char(self) -> char {
+        match self {
+            Cell::Empty => '.',
+            Cell::Corrupted => '#',
+            Cell::Start => 'S',
+            Cell::End => 'O',
+        }
+    }
+}
+
+impl std::fmt::Display for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+impl std::fmt::Debug for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(self, f)
+    }
+}
+
+fn parse_input(input: &str) -> Result<Vec<(usize, usize)>, AocError> {
+    input
+        .lines()
+        .enumerate()
+        .map(|(i, line)| {
+            let (x, y) = line
+                .split_once(',')
+                .ok_or_else(|| AocError::parse(i + 1, 1, "Expected coordinates like 5,4"))?;
+            Ok((
+                parse_token(i, line, x.trim())?,
+                parse_token(i, line, y.trim())?,
+            ))
+        })
+        .collect()
+}
+
+/// Rejects bytes that would fall outside the memory space, pointing at the offending line.
+fn check_bounds(bytes: &[(usize, usize)], width: usize, height: usize) -> Result<(), AocError> {
+    match bytes.iter().position(|&(x, y)| x >= width || y >= height) {
+        Some(i) => Err(AocError::parse(
+            i + 1,
+            1,
+            format!("Byte is outside the {width}x{height} memory space"),
+        )),
+        None => Ok(()),
+    }
+}
+
+fn build_grid(
+    bytes: &[(usize, usize)],
+    width: usize,
+    height: usize,
+    nbytes: usize,
+) -> Result<Grid<Cell>, AocError> {
+    check_bounds(bytes, width, height)?;
+    let mut grid = bytes.iter().take(nbytes).fold(
+        Grid::new(width, height, Cell::Empty),
+        |mut grid, &(x, y)| {
+            grid[(x, y)] = Cell::Corrupted;
+            grid
+        },
+    );
+    grid[(0, 0)] = Cell::Start;
+    grid[(width - 1, height - 1)] = Cell::End;
+
+    Ok(grid)
+}
+
+fn solver(
+    bytes: &[(usize, usize)],
+    width: usize,
+    height: usize,
+    nbytes: usize,
+) -> Result<usize, AocError> {
+    let grid = build_grid(bytes, width, height, nbytes)?;
+    let end = (width - 1, height - 1);
+    let passable = |&cell: &Cell| cell != Cell::Corrupted;
+    let manhattan = |xy: (usize, usize)| Point::from(xy).manhattan(end.into());
+    astar(&grid, (0, 0), end, passable, |_, _| 1, manhattan)
+        .distance(State::new(end, 0))
+        .ok_or_else(|| AocError::unsolvable("No path from start to end"))
+}
+
+/// The memory space as a graph of orthogonally adjacent cells.
+fn grid_graph(width: usize, height: usize) -> Graph<(usize, usize)> {
+    let mut graph = Graph::undirected();
+    for (x, y) in iproduct!(0..width, 0..height) {
+        graph.add_node((x, y));
+        if x + 1 < width {
+            graph.add_edge((x, y), (x + 1, y));
+        }
+        if y + 1 < height {
+            graph.add_edge((x, y), (x, y + 1));
+        }
+    }
+    graph
+}
+
+fn part2(bytes: &[(usize, usize)], width: usize, height: usize) -> Result<String, AocError> {
+    check_bounds(bytes, width, height)?;
+    let mut graph = grid_graph(width, height);
+    let end = (width - 1, height - 1);
+    let shortest_path = |graph: &Graph<(usize, usize)>| -> Option<HashSet<(usize, usize)>> {
+        let path = graph.bfs((0, 0)).shortest_path_where(|&xy| xy == end)?;
+        Some(path.into_iter().collect())
+    };
+
+    // Drop the bytes one at a time, only searching again when one lands on the current path
+    let mut path = shortest_path(&graph);
+    for &byte in bytes {
+        graph.remove_node(&byte);
+        if path.as_ref().is_some_and(|path| path.contains(&byte)) {
+            path = shortest_path(&graph);
+        }
+        if path.is_none() {
+            let (x, y) = byte;
+            return Ok(format!("{x},{y}"));
+        }
+    }
+    Err(AocError::unsolvable("No byte blocks the path"))
+}
+
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Vec<(usize, usize)>;
+    type Answer1 = usize;
+    type Answer2 = String;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize, AocError> {
+        solver(input, WIDTH, HEIGHT, NBYTES)
+    }
+
+    fn part2(input: &Self::Input) -> Result<String, AocError> {
+        part2(input, WIDTH, HEIGHT)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r#"5,4
+4,2
+4,5
+3,0
+2,1
+6,3
+2,4
+1,5
+0,6
+3,3
+2,6
+5,1
+1,2
+5,5
+2,5
+6,5
+1,4
+0,4
+6,4
+1,1
+6,1
+1,0
+0,5
+1,6
+2,0"#;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(
+            solver(&parse_input(EXAMPLE).unwrap(), 7, 7, 12).unwrap(),
+            22
+        );
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(&parse_input(EXAMPLE).unwrap(), 7, 7).unwrap(), "6,1");
+    }
+
+    #[test]
+    fn test_invalid_input() {
+        assert_eq!(
+            parse_input("1,2\n3;4"),
+            Err(AocError::parse(2, 1, "Expected coordinates like 5,4"))
+        );
+        assert!(matches!(
+            parse_input("1,2\n3,x"),
+            Err(AocError::Parse {
+                line: 2,
+                column: 3,
+                ..
+            })
+        ));
+
+        let bytes = parse_input("1,2\n7,0").unwrap();
+        assert!(matches!(
+            solver(&bytes, 7, 7, 1),
+            Err(AocError::Parse { line: 2, .. })
+        ));
+        assert!(matches!(
+            part2(&bytes, 7, 7),
+            Err(AocError::Parse { line: 2, .. })
+        ));
+    }
+}
@@ -0,0 +1,99 @@
+use crate::{AocError, Solution};
+use itertools::Itertools;
+
+type ParserOutput = Result<(Vec<String>, Vec<String>), AocError>;
+
+fn parse_input(input: &str) -> ParserOutput {
+    let (patterns, designs) = input
+        .split_once("\n\n")
+        .ok_or(AocError::Missing("designs"))?;
+    let patterns = patterns.split(", ").sorted().map(String::from).collect();
+    let designs = designs.lines().sorted().map(String::from).collect();
+
+    Ok((patterns, designs))
+}
+
+fn count_ways(design: &str, patterns: &[String]) -> usize {
+    let n = design.len();
+    let mut dp: Vec<usize> = vec![0; n + 1];
+    dp[0] = 1; // One way to make empty string
+
+    for i in 0..n {
+        // Skip if no ways to reach this position
+        if dp[i] == 0 {
+            continue;
+        }
+
+        // Try each pattern
+        for pattern in patterns {
+            if design[i..].starts_with(pattern) {
+                let new_pos = i + pattern.len();
+
+                // Add number of ways to reach current position
+                dp[new_pos] += dp[i];
+            }
+        }
+    }
+
+    dp[n]
+}
+
+fn part1((patterns, designs): &(Vec<String>, Vec<String>)) -> usize {
+    designs
+        .iter()
+        .filter(|design| count_ways(design, patterns) > 0)
+        .count()
+}
+
+fn part2((patterns, designs): &(Vec<String>, Vec<String>)) -> usize {
+    designs
+        .iter()
+        .map(|design| count_ways(design, patterns))
+        .sum()
+}
+
+pub struct Day19;
+
+impl Solution for Day19 {
+    type Input = (Vec<String>, Vec<String>);
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        parse_input(input)
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(part1(input))
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize, AocError> {
+        Ok(part2(input))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r#"r, wr, b, g, bwu, rb, gb, br
+
+brwrr
+bggr
+gbbr
+rrbgbr
+ubwu
+bwurrg
+brgr
+bbrgwb"#;
+
+    #[test]
+    fn test_part1() {
+        assert_eq!(part1(&parse_input(EXAMPLE).unwrap()), 6);
+    }
+
+    #[test]
+    fn test_part2() {
+        assert_eq!(part2(&parse_input(EXAMPLE).unwrap()), 16);
+    }
+}
@@ -0,0 +1,192 @@
+use crate::graph::Dijkstra;
+use crate::{AocError, Direction, Grid, Point, Solution};
+use rayon::prelude::*;
+
+const TIME_SAVED: usize = 100;
+
+type Coordinate = Point<usize>;
+
+#[derive(Default, Clone, Copy, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Cell {
+    #[default]
+    Empty,
+    Wall,
+    Start,
+    End,
+}
+
+impl Cell {
+    fn to_char(self) -> char {
+        match self {
+            Cell::Empty => '.',
+            Cell::Wall => '#',
+            Cell::Start => 'S',
+            Cell::End => 'E',
+        }
+    }
+}
+
+impl TryFrom<char> for Cell {
+    type Error = String;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        match c {
+            '#' => Ok(Cell::Wall),
+            'S' => Ok(Cell::Start),
+            'E' => Ok(Cell::End),
+            '.' => Ok(Cell::Empty),
+            _ => Err(format!("Invalid character: {}", c)),
+        }
+    }
+}
+
+impl std::fmt::Display for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+impl std::fmt::Debug for Cell {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        std::fmt::Display::fmt(self, f)
+    }
+}
+
+fn find_thing(
+    grid: &Grid<Cell>,
+    query: Cell,
+    what: &'static str,
+) -> Result<(usize, usize), AocError> {
+    grid.iter()
+        .enumerate()
+        .find(|(_, &cell_type)| cell_type == query)
+        .map(|(idx, _)| grid.idx_to_xy(idx))
+        .ok_or(AocError::Missing(what))
+}
+
+fn solver(
+    grid: &Grid<Cell>,
+    time_saved: usize,
+    max_cheat_duration: usize,
+) -> Result<usize, AocError> {
+    let start = find_thing(grid, Cell::Start, "start")?;
+    let end = find_thing(grid, Cell::End, "end")?;
+
+    let track = |xy: &(usize, usize)| {
+        Direction::cardinal()
+            .filter_map(|d| grid.step(*xy, d))
+            .filter(|&next| grid[next] != Cell::Wall)
+            .map(|next| (next, 1))
+            .collect::<Vec<_>>()
+    };
+    let from_start = Dijkstra::new(&track, start).run_to_completion();
+    let from_end = Dijkstra::new(&track, end).run_to_completion();
+    let best = from_start
+        .distance_to(&end)
+        .ok_or_else(|| AocError::unsolvable("No path from start to end"))?;
+
+    let reach = max_cheat_duration as isize;
+    let cheats: Vec<Point<isize>> = (-reach..=reach)
+        .flat_map(|dy| {
+            let remaining = reach - dy.abs();
+            (-remaining..=remaining).map(move |dx| Point::new(dx, dy))
+        })
+        .collect();
+
+    let on_track: Vec<(Coordinate, usize)> = from_start
+        .iter()
+        .map(|(&xy, distance)| (Coordinate::from(xy), distance))
+        .collect();
+    // A cheat from `a` to `b` costs the race to `a`, the cheat itself, and the race from `b`
+    Ok(on_track
+        .par_iter()
+        .map(|&(from, to_cheat)| {
+            cheats
+                .iter()
+                .filter_map(|&offset| from.checked_add_signed(offset))
+                .filter(|&to| {
+                    from_end.distance_to(&to.into()).is_some_and(|rest| {
+                        to_cheat + from.manhattan(to) + rest + time_saved <= best
+                    })
+                })
+                .count()
+        })
+        .sum())
+}
+
+pub struct Day20;
+
+impl Solution for Day20 {
+    type Input = Grid<Cell>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError> {
+        Grid::parse_str(input, Cell::try_from, Cell::default())
+    }
+
+    fn part1(input: &Self::Input) -> Result<usize, AocError> {
+        solver(input, TIME_SAVED, 2)
+    }
+
+    fn part2(input: &Self::Input) -> Result<usize, AocError> {
+        solver(input, TIME_SAVED, 20)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXAMPLE: &str = r#"###############
+#...#...#.....#
+#.#.#.#.#.###.#
+#S#...#.#.#...#
+#######.#.#.###
+#######.#.#...#
+#######.#.###.#
+###..E#...#...#
+###.#######.###
+#...###...#...#
+#.#####.#.###.#
+#.#...#.#.#...#
+#.#.#.#.#.#.###
+#...#...#...###
+###############"#;
+
+    #[test]
+    fn test_example1() {
+        let grid = Day20::parse(EXAMPLE).unwrap();
+        assert_eq!(solver(&grid, 19, 2).unwrap(), 5);
+        assert_eq!(solver(&grid, 20, 2).unwrap(), 5);
+        assert_eq!(solver(&grid, 21, 2).unwrap(), 4);
+        assert_eq!(solver(&grid, 35, 2).unwrap(), 4);
+        assert_eq!(solver(&grid, 36, 2).unwrap(), 4);
+        assert_eq!(solver(&grid, 37, 2).unwrap(), 3);
+        assert_eq!(solver(&grid, 38, 2).unwrap(), 3);
+        assert_eq!(solver(&grid, 39, 2).unwrap(), 2);
+        assert_eq!(solver(&grid, 40, 2).unwrap(), 2);
+        assert_eq!(solver(&grid, 41, 2).unwrap(), 1);
+        assert_eq!(solver(&grid, 64, 2).unwrap(), 1);
+        assert_eq!(solver(&grid, 65, 2).unwrap(), 0);
+    }
+
+    #[test]
+    fn test_example2() {
+        let grid = Day20::parse(EXAMPLE).unwrap();
+        assert_eq!(solver(&grid, 50, 20).unwrap(), 285);
+        assert_eq!(solver(&grid, 52, 20).unwrap(), 253);
+        assert_eq!(solver(&grid, 54, 20).unwrap(), 222);
+        assert_eq!(solver(&grid, 56, 20).unwrap(), 193);
+        assert_eq!(solver(&grid, 58, 20).unwrap(), 154);
+        assert_eq!(solver(&grid, 60, 20).unwrap(), 129);
+        assert_eq!(solver(&grid, 62, 20).unwrap(), 106);
+        assert_eq!(solver(&grid, 64, 20).unwrap(), 86);
+        assert_eq!(solver(&grid, 66, 20).unwrap(), 67);
+        assert_eq!(solver(&grid, 68, 20).unwrap(), 55);
+        assert_eq!(solver(&grid, 70, 20).unwrap(), 41);
+        assert_eq!(solver(&grid, 72, 20).unwrap(), 29);
+        assert_eq!(solver(&grid, 74, 20).unwrap(), 7);
+        assert_eq!(solver(&grid, 76, 20).unwrap(), 3);
+    }
+}
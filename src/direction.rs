@@ -0,0 +1,231 @@
+use crate::Point;
+use bitflags::bitflags;
+
+/// One of the 8 compass directions on a grid whose `y` grows downwards.
+///
+/// Variants are ordered clockwise starting from `Up`.
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Direction {
+    Up,
+    UpRight,
+    Right,
+    DownRight,
+    Down,
+    DownLeft,
+    Left,
+    UpLeft,
+}
+
+impl Direction {
+    /// Up, right, down and left.
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::Up,
+        Direction::Right,
+        Direction::Down,
+        Direction::Left,
+    ];
+
+    /// All 8 directions, clockwise from `Up`.
+    pub const ALL: [Direction; 8] = [
+        Direction::Up,
+        Direction::UpRight,
+        Direction::Right,
+        Direction::DownRight,
+        Direction::Down,
+        Direction::DownLeft,
+        Direction::Left,
+        Direction::UpLeft,
+    ];
+
+    pub fn cardinal() -> impl Iterator<Item = Direction> {
+        Self::CARDINAL.into_iter()
+    }
+
+    pub fn all() -> impl Iterator<Item = Direction> {
+        Self::ALL.into_iter()
+    }
+
+    pub fn is_cardinal(self) -> bool {
+        (self as u8).is_multiple_of(2)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        !self.is_cardinal()
+    }
+
+    /// Rotates clockwise by `eighths` of a full turn.
+    fn rotate(self, eighths: u8) -> Direction {
+        Self::ALL[(self as usize + eighths as usize) % 8]
+    }
+
+    /// Turns 90° clockwise.
+    pub fn turn_right(self) -> Direction {
+        self.rotate(2)
+    }
+
+    /// Turns 90° counter-clockwise.
+    pub fn turn_left(self) -> Direction {
+        self.rotate(6)
+    }
+
+    /// Turns 45° clockwise.
+    pub fn turn_right_45(self) -> Direction {
+        self.rotate(1)
+    }
+
+    /// Turns 45° counter-clockwise.
+    pub fn turn_left_45(self) -> Direction {
+        self.rotate(7)
+    }
+
+    pub fn reverse(self) -> Direction {
+        self.rotate(4)
+    }
+
+    /// The unit step in this direction.
+    pub fn offset(self) -> Point<isize> {
+        match self {
+            Direction::Up => Point::new(0, -1),
+            Direction::UpRight => Point::new(1, -1),
+            Direction::Right => Point::new(1, 0),
+            Direction::DownRight => Point::new(1, 1),
+            Direction::Down => Point::new(0, 1),
+            Direction::DownLeft => Point::new(-1, 1),
+            Direction::Left => Point::new(-1, 0),
+            Direction::UpLeft => Point::new(-1, -1),
+        }
+    }
+
+    /// Parses `^`, `>`, `v` and `<`, and the arrows `↗`, `↘`, `↙` and `↖` for diagonals.
+    pub fn from_char(c: char) -> Option<Direction> {
+        Self::ALL
+            .into_iter()
+            .find(|direction| direction.to_char() == c)
+    }
+
+    pub fn to_char(self) -> char {
+        match self {
+            Direction::Up => '^',
+            Direction::UpRight => '↗',
+            Direction::Right => '>',
+            Direction::DownRight => '↘',
+            Direction::Down => 'v',
+            Direction::DownLeft => '↙',
+            Direction::Left => '<',
+            Direction::UpLeft => '↖',
+        }
+    }
+
+    pub fn flag(self) -> Directions {
+        Directions::from_bits_retain(1 << self as u8)
+    }
+}
+
+impl TryFrom<char> for Direction {
+    type Error = String;
+
+    fn try_from(c: char) -> Result<Self, Self::Error> {
+        Direction::from_char(c).ok_or_else(|| format!("Invalid direction: {c}"))
+    }
+}
+
+impl std::fmt::Display for Direction {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
+
+bitflags! {
+    /// A set of [`Direction`]s, e.g. the directions a cell has been entered from.
+    #[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+    pub struct Directions: u8 {
+        const UP         = 1 << Direction::Up as u8;
+        const UP_RIGHT   = 1 << Direction::UpRight as u8;
+        const RIGHT      = 1 << Direction::Right as u8;
+        const DOWN_RIGHT = 1 << Direction::DownRight as u8;
+        const DOWN       = 1 << Direction::Down as u8;
+        const DOWN_LEFT  = 1 << Direction::DownLeft as u8;
+        const LEFT       = 1 << Direction::Left as u8;
+        const UP_LEFT    = 1 << Direction::UpLeft as u8;
+    }
+}
+
+impl Directions {
+    pub fn contains_direction(self, direction: Direction) -> bool {
+        self.contains(direction.flag())
+    }
+
+    /// The directions in the set, clockwise from `Up`.
+    pub fn directions(self) -> impl Iterator<Item = Direction> {
+        Direction::all().filter(move |&direction| self.contains_direction(direction))
+    }
+}
+
+impl From<Direction> for Directions {
+    fn from(direction: Direction) -> Self {
+        direction.flag()
+    }
+}
+
+impl FromIterator<Direction> for Directions {
+    fn from_iter<I: IntoIterator<Item = Direction>>(iter: I) -> Self {
+        iter.into_iter()
+            .fold(Directions::empty(), |set, direction| set | direction.flag())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_turning() {
+        assert_eq!(Direction::Up.turn_right(), Direction::Right);
+        assert_eq!(Direction::Up.turn_left(), Direction::Left);
+        assert_eq!(Direction::Left.turn_right_45(), Direction::UpLeft);
+        assert_eq!(Direction::Up.turn_left_45(), Direction::UpLeft);
+        assert_eq!(Direction::DownRight.reverse(), Direction::UpLeft);
+
+        for direction in Direction::all() {
+            assert_eq!(direction.turn_right().turn_left(), direction);
+            assert_eq!(direction.reverse().reverse(), direction);
+            assert_eq!(
+                direction.turn_right().offset(),
+                direction.offset().rotate_cw()
+            );
+            assert_eq!(direction.reverse().offset(), -direction.offset());
+        }
+    }
+
+    #[test]
+    fn test_cardinal_and_diagonal() {
+        assert!(Direction::cardinal().all(Direction::is_cardinal));
+        assert_eq!(Direction::all().filter(|d| d.is_diagonal()).count(), 4);
+        for direction in Direction::all() {
+            let offset = direction.offset();
+            assert_eq!(direction.is_cardinal(), offset.x == 0 || offset.y == 0);
+        }
+    }
+
+    #[test]
+    fn test_chars() {
+        for direction in Direction::all() {
+            assert_eq!(Direction::from_char(direction.to_char()), Some(direction));
+        }
+        assert_eq!(Direction::try_from('v'), Ok(Direction::Down));
+        assert!(Direction::try_from('x').is_err());
+    }
+
+    #[test]
+    fn test_direction_set() {
+        let set: Directions = [Direction::Left, Direction::Up].into_iter().collect();
+        assert_eq!(set, Directions::UP | Directions::LEFT);
+        assert!(set.contains_direction(Direction::Up));
+        assert!(!set.contains_direction(Direction::Down));
+        assert_eq!(
+            set.directions().collect::<Vec<_>>(),
+            vec![Direction::Up, Direction::Left]
+        );
+        assert_eq!(Directions::all().directions().count(), 8);
+    }
+}
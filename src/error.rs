@@ -0,0 +1,92 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum AocError {
+    /// The input could not be parsed. `line` and `column` are 1-based.
+    Parse {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// Something the puzzle needs, such as a start tile or a robot, is not in the input.
+    Missing(&'static str),
+    /// The input parsed, but has no answer.
+    Unsolvable(String),
+    /// A graph operation was used on a graph that does not support it.
+    InvalidGraph(String),
+}
+
+impl AocError {
+    pub fn parse(line: usize, column: usize, message: impl Into<String>) -> Self {
+        AocError::Parse {
+            line,
+            column,
+            message: message.into(),
+        }
+    }
+
+    pub fn unsolvable(message: impl Into<String>) -> Self {
+        AocError::Unsolvable(message.into())
+    }
+
+    pub fn invalid_graph(message: impl Into<String>) -> Self {
+        AocError::InvalidGraph(message.into())
+    }
+}
+
+impl Display for AocError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AocError::Parse {
+                line,
+                column,
+                message,
+            } => write!(f, "Parse error at line {line}, column {column}: {message}"),
+            AocError::Missing(what) => write!(f, "No {what} found in input"),
+            AocError::Unsolvable(message) => write!(f, "No solution: {message}"),
+            AocError::InvalidGraph(message) => write!(f, "Invalid graph operation: {message}"),
+        }
+    }
+}
+
+impl std::error::Error for AocError {}
+
+/// Parses `token`, which must be a slice of `line`, reporting its position on failure.
+///
+/// `line_idx` is the 0-based index of `line` within the input.
+pub fn parse_token<T>(line_idx: usize, line: &str, token: &str) -> Result<T, AocError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    token.parse().map_err(|e: T::Err| {
+        let column = (token.as_ptr() as usize)
+            .checked_sub(line.as_ptr() as usize)
+            .filter(|&offset| offset <= line.len())
+            .map_or(1, |offset| offset + 1);
+        AocError::parse(line_idx + 1, column, format!("{e}: {token:?}"))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_token_reports_column() {
+        let line = "12   x4";
+        let token = line.split_whitespace().nth(1).unwrap();
+        let err = parse_token::<i32>(2, line, token).unwrap_err();
+
+        match err {
+            AocError::Parse { line, column, .. } => assert_eq!((line, column), (3, 6)),
+            other => panic!("unexpected error {other:?}"),
+        }
+    }
+
+    #[test]
+    fn test_parse_token_ok() {
+        assert_eq!(parse_token::<u8>(0, "7", "7"), Ok(7));
+    }
+}
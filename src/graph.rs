@@ -1,3 +1,4 @@
+use crate::{AocError, UnionFind};
 use itertools::Itertools;
 use num_traits::{Bounded, NumOps, One, Zero};
 use std::cmp::Ordering;
@@ -5,6 +6,7 @@ use std::collections::BinaryHeap;
 use std::collections::{HashMap, HashSet, VecDeque};
 use std::fmt::{Debug, Display};
 use std::hash::Hash;
+use std::marker::PhantomData;
 use std::ops::{Add, AddAssign};
 
 pub trait Weight:
@@ -74,7 +76,7 @@ where
             self.adjacency_map
                 .entry(from.clone())
                 .or_default()
-                .insert(to.clone(), weight.clone());
+                .insert(to.clone(), weight);
             self.adjacency_map
                 .entry(to)
                 .or_default()
@@ -82,6 +84,122 @@ where
         }
     }
 
+    /// Removes the edge, in both directions for an undirected graph, returning its weight.
+    pub fn remove_edge(&mut self, from: &N, to: &N) -> Option<W> {
+        let weight = self.adjacency_map.get_mut(from)?.remove(to)?;
+        if !self.directed {
+            if let Some(edges) = self.adjacency_map.get_mut(to) {
+                edges.remove(from);
+            }
+        }
+        Some(weight)
+    }
+
+    /// Removes `node` and every edge touching it. Returns `false` if it was not in the graph.
+    pub fn remove_node(&mut self, node: &N) -> bool {
+        let edges = self.adjacency_map.remove(node);
+        let mut found = edges.is_some();
+
+        if self.directed {
+            for edges in self.adjacency_map.values_mut() {
+                found |= edges.remove(node).is_some();
+            }
+        } else {
+            for neighbor in edges.into_iter().flat_map(|edges| edges.into_keys()) {
+                if let Some(edges) = self.adjacency_map.get_mut(&neighbor) {
+                    edges.remove(node);
+                }
+            }
+        }
+        found
+    }
+
+    /// Keeps only the nodes matching `keep`, along with the edges between them.
+    pub fn retain_nodes<F>(&mut self, mut keep: F)
+    where
+        F: FnMut(&N) -> bool,
+    {
+        let removed: HashSet<N> = self
+            .nodes()
+            .into_iter()
+            .filter(|node| !keep(node))
+            .cloned()
+            .collect();
+        self.adjacency_map.retain(|node, _| !removed.contains(node));
+        for edges in self.adjacency_map.values_mut() {
+            edges.retain(|to, _| !removed.contains(to));
+        }
+    }
+
+    /// Merges `to` into `from` along the edge between them: `to`'s other edges are moved over
+    /// to `from` and `to` is removed. Where both already had an edge to the same node, the
+    /// lighter weight is kept. Returns `false`, leaving the graph unchanged, if there is no
+    /// edge from `from` to `to`.
+    pub fn contract_edge(&mut self, from: &N, to: &N) -> bool {
+        if from == to || self.remove_edge(from, to).is_none() {
+            return false;
+        }
+
+        let outgoing = self.adjacency_map.remove(to).unwrap_or_default();
+        let incoming: Vec<(N, W)> = if self.directed {
+            self.adjacency_map
+                .iter_mut()
+                .filter_map(|(source, edges)| Some((source.clone(), edges.remove(to)?)))
+                .collect()
+        } else {
+            outgoing
+                .iter()
+                .map(|(neighbor, &weight)| {
+                    if let Some(edges) = self.adjacency_map.get_mut(neighbor) {
+                        edges.remove(to);
+                    }
+                    (neighbor.clone(), weight)
+                })
+                .collect()
+        };
+
+        let merge = |graph: &mut Self, a: N, b: N, weight: W| {
+            if a != b && graph.get_weight(&a, &b).is_none_or(|&w| weight < w) {
+                graph.add_edge_weighted(a, b, weight);
+            }
+        };
+        for (next, weight) in outgoing {
+            merge(self, from.clone(), next, weight);
+        }
+        if self.directed {
+            for (source, weight) in incoming {
+                merge(self, source, from.clone(), weight);
+            }
+        }
+        true
+    }
+
+    /// The number of edges leaving `node`. For an undirected graph this is its degree.
+    pub fn out_degree(&self, node: &N) -> usize {
+        self.adjacency_map.get(node).map_or(0, HashMap::len)
+    }
+
+    /// The number of edges arriving at `node`. For an undirected graph this is its degree.
+    pub fn in_degree(&self, node: &N) -> usize {
+        if self.directed {
+            self.adjacency_map
+                .values()
+                .filter(|edges| edges.contains_key(node))
+                .count()
+        } else {
+            self.out_degree(node)
+        }
+    }
+
+    /// The number of edges touching `node`, counting both directions in a directed graph.
+    pub fn degree(&self, node: &N) -> usize {
+        if self.directed {
+            self.in_degree(node) + self.out_degree(node)
+        } else {
+            self.out_degree(node)
+        }
+    }
+
     pub fn nodes(&self) -> Vec<&N> {
         self.adjacency_map
             .keys()
@@ -106,7 +224,7 @@ where
     pub fn has_edge(&self, from: &N, to: &N) -> bool {
         self.adjacency_map
             .get(from)
-            .map_or(false, |edges| edges.contains_key(to))
+            .is_some_and(|edges| edges.contains_key(to))
     }
 
     pub fn get_weight(&self, from: &N, to: &N) -> Option<&W> {
@@ -127,15 +245,38 @@ where
         Bfs::new(self, start)
     }
 
+    /// Breadth-first search from several sources at once, visiting nodes in order of their
+    /// distance to the nearest source. See [`Bfs::source_of`] to tell which source that was.
+    pub fn bfs_from<I>(&self, sources: I) -> Bfs<'_, N, W>
+    where
+        I: IntoIterator<Item = N>,
+    {
+        Bfs::from_sources(self, sources)
+    }
+
+    pub fn dfs(&self, start: N) -> Dfs<'_, N, W> {
+        Dfs::new(self, start)
+    }
+
+    /// Depth-first search from each of `roots` in turn, skipping any already visited. Passing
+    /// every node visits the whole graph.
+    pub fn dfs_from<I>(&self, roots: I) -> Dfs<'_, N, W>
+    where
+        I: IntoIterator<Item = N>,
+    {
+        Dfs::from_roots(self, roots)
+    }
+
     pub fn subgraph(&self, nodes: &[N]) -> Graph<N, W> {
         let mut subgraph: Graph<N, W> = Graph::new(self.directed);
+        let keep: HashSet<&N> = nodes.iter().collect();
 
         for from in nodes {
             subgraph.add_node(from.clone());
             if let Some(neighbors) = self.neighbors_weighted(from) {
                 for (to, weight) in neighbors {
-                    if nodes.contains(to) {
-                        subgraph.add_edge_weighted(from.clone(), to.clone(), weight.clone());
+                    if keep.contains(to) {
+                        subgraph.add_edge_weighted(from.clone(), to.clone(), *weight);
                     }
                 }
             }
@@ -143,30 +284,308 @@ where
         subgraph
     }
 
-    pub fn connected_components(
-        &self,
-    ) -> Result<impl Iterator<Item = Graph<N, W>> + '_, &'static str> {
+    /// Orders the nodes so that every edge points forwards, or reports a cycle if there is no
+    /// such order. This is a reversed depth-first post-order, which is deterministic but does
+    /// not put smaller nodes first; [`topological_layers`](Self::topological_layers) sorts
+    /// within each layer. In an undirected graph every edge is a cycle of two nodes.
+    pub fn topological_sort(&self) -> Result<Vec<N>, Cycle<N>> {
+        let mut order = Vec::new();
+        // The current DFS path, to read a cycle off when a back edge closes one
+        let mut path = Vec::new();
+
+        for event in self.dfs_from(self.nodes().into_iter().cloned()) {
+            match event {
+                DfsEvent::Enter(node) => path.push(node),
+                DfsEvent::Exit(node) => {
+                    path.pop();
+                    order.push(node);
+                }
+                DfsEvent::Edge {
+                    to,
+                    kind: EdgeKind::Back,
+                    ..
+                } => {
+                    let start = path.iter().rposition(|node| *node == to).unwrap_or(0);
+                    return Err(Cycle(path.split_off(start)));
+                }
+                DfsEvent::Edge { .. } => {}
+            }
+        }
+
+        order.reverse();
+        Ok(order)
+    }
+
+    /// Groups the nodes into layers where each layer only depends on earlier ones: first every
+    /// node without incoming edges, then every node whose predecessors are all in the first
+    /// layer, and so on. Each layer is sorted. Fails with a cycle if some nodes are never laid
+    /// out.
+    pub fn topological_layers(&self) -> Result<Vec<Vec<N>>, Cycle<N>> {
+        let mut in_degrees: HashMap<&N, usize> =
+            self.nodes().into_iter().map(|node| (node, 0)).collect();
+        for (_, to) in self.edge_pairs() {
+            *in_degrees.entry(to).or_default() += 1;
+        }
+
+        let mut layers = Vec::new();
+        let mut layer: Vec<&N> = in_degrees
+            .iter()
+            .filter(|&(_, &degree)| degree == 0)
+            .map(|(&node, _)| node)
+            .sorted()
+            .collect();
+
+        while !layer.is_empty() {
+            let mut next = Vec::new();
+            for node in &layer {
+                in_degrees.remove(node);
+                for to in self
+                    .adjacency_map
+                    .get(*node)
+                    .into_iter()
+                    .flat_map(|m| m.keys())
+                {
+                    let degree = in_degrees.entry(to).or_default();
+                    *degree -= 1;
+                    if *degree == 0 {
+                        next.push(to);
+                    }
+                }
+            }
+            layers.push(layer.into_iter().cloned().collect());
+            next.sort();
+            layer = next;
+        }
+
+        match in_degrees.keys().min() {
+            None => Ok(layers),
+            Some(&stuck) => Err(self.cycle_into(stuck, &in_degrees)),
+        }
+    }
+
+    /// A cycle among the nodes that [`topological_layers`](Self::topological_layers) could not
+    /// lay out, starting from its smallest node. Each of them still has a predecessor among
+    /// them, so walking back through those must eventually repeat a node.
+    fn cycle_into(&self, start: &N, remaining: &HashMap<&N, usize>) -> Cycle<N> {
+        let mut walk = vec![start];
+        loop {
+            let node = walk[walk.len() - 1];
+            let predecessor = remaining
+                .keys()
+                .filter(|from| self.has_edge(from, node))
+                .min()
+                .expect("every remaining node has a remaining predecessor");
+            if let Some(repeat) = walk.iter().position(|n| n == predecessor) {
+                // The walk runs against the edges, so reverse it into edge order
+                let mut cycle: Vec<N> = walk.split_off(repeat).into_iter().rev().cloned().collect();
+                let smallest = cycle.iter().position_min().unwrap_or(0);
+                cycle.rotate_left(smallest);
+                return Cycle(cycle);
+            }
+            walk.push(predecessor);
+        }
+    }
+
+    /// Tarjan's algorithm: groups of nodes that can all reach each other. Each component is
+    /// sorted, and components come in reverse topological order, so no component has an edge
+    /// to a later one.
+    pub fn strongly_connected_components(&self) -> Vec<Vec<N>> {
+        let mut components = Vec::new();
+        // Nodes visited but not yet assigned to a component
+        let mut stack = Vec::new();
+        let mut on_stack = HashSet::new();
+        let mut path: Vec<N> = Vec::new();
+        let mut index: HashMap<N, usize> = HashMap::new();
+        // The smallest index reachable from each node through nodes still on the stack
+        let mut low: HashMap<N, usize> = HashMap::new();
+
+        for event in self.dfs_from(self.nodes().into_iter().cloned()) {
+            match event {
+                DfsEvent::Enter(node) => {
+                    index.insert(node.clone(), index.len());
+                    low.insert(node.clone(), index[&node]);
+                    stack.push(node.clone());
+                    on_stack.insert(node.clone());
+                    path.push(node);
+                }
+                DfsEvent::Edge { from, to, kind } => {
+                    if kind != EdgeKind::Tree && on_stack.contains(&to) {
+                        let reachable = low[&from].min(index[&to]);
+                        low.insert(from, reachable);
+                    }
+                }
+                DfsEvent::Exit(node) => {
+                    path.pop();
+                    if low[&node] == index[&node] {
+                        let mut component = Vec::new();
+                        while let Some(member) = stack.pop() {
+                            on_stack.remove(&member);
+                            let done = member == node;
+                            component.push(member);
+                            if done {
+                                break;
+                            }
+                        }
+                        component.sort();
+                        components.push(component);
+                    }
+                    if let Some(parent) = path.last() {
+                        let reachable = low[parent].min(low[&node]);
+                        low.insert(parent.clone(), reachable);
+                    }
+                }
+            }
+        }
+        components
+    }
+
+    /// Groups of nodes connected when edge directions are ignored. Each component is sorted,
+    /// and components are ordered by their smallest node.
+    pub fn weakly_connected_components(&self) -> Vec<Vec<N>> {
+        let mut sets: UnionFind<&N> = self.adjacency_map.keys().collect();
+        for (from, to) in self.edge_pairs() {
+            sets.union(from, to);
+        }
+
+        let mut components: Vec<Vec<N>> = sets
+            .sets()
+            .into_iter()
+            .map(|set| set.into_iter().cloned().sorted().collect())
+            .collect();
+        components.sort_unstable_by(|a, b| a[0].cmp(&b[0]));
+        components
+    }
+
+    /// Collapses each strongly connected component into a single node, leaving a DAG.
+    pub fn condensation(&self) -> Condensation<N, W> {
+        let mut components = self.strongly_connected_components();
+        // Tarjan finds components in reverse topological order
+        components.reverse();
+
+        let component_of: HashMap<N, usize> = components
+            .iter()
+            .enumerate()
+            .flat_map(|(id, component)| component.iter().map(move |node| (node.clone(), id)))
+            .collect();
+
+        let mut dag = Graph::directed();
+        for id in 0..components.len() {
+            dag.add_node(id);
+        }
+        for (from, to, &weight) in self.edges() {
+            let (from, to) = (component_of[from], component_of[to]);
+            if from != to && dag.get_weight(&from, &to).is_none_or(|&w| weight < w) {
+                dag.add_edge_weighted(from, to, weight);
+            }
+        }
+
+        Condensation {
+            dag,
+            components,
+            component_of,
+        }
+    }
+
+    /// Collapses corridors: every chain of nodes with exactly two neighbours becomes a single
+    /// edge, weighted by the chain's total length, between the junctions or dead ends at its
+    /// ends. Nodes matching `pinned` are always kept, e.g. a maze's start and end.
+    ///
+    /// Where two chains join the same pair of nodes only the shorter is kept, and chains that
+    /// loop back to where they started are dropped, as neither can be part of a shortest route.
+    /// A cycle with no junction or pinned node on it disappears entirely.
+    pub fn contract_chains<F>(&self, pinned: F) -> Result<ContractedGraph<N, W>, AocError>
+    where
+        F: Fn(&N) -> bool,
+    {
         if self.directed {
-            return Err("Cannot find connected components of a directed graph");
+            return Err(AocError::invalid_graph(
+                "Cannot contract chains of a directed graph",
+            ));
         }
 
-        // Note: Iterate in rev order so that we pop from front of nodes
-        let mut nodes: Vec<N> = self.nodes().into_iter().cloned().rev().collect();
-        let mut visited = HashSet::new();
+        let is_kept =
+            |node: &N| pinned(node) || self.degree(node) != 2 || self.has_edge(node, node);
+        let mut graph = Graph::undirected();
+        let mut chains = HashMap::new();
+
+        for (start, edges) in self.adjacency_map.iter().filter(|(node, _)| is_kept(node)) {
+            graph.add_node(start.clone());
+
+            for (first, &weight) in edges {
+                let (mut previous, mut current, mut length) = (start, first, weight);
+                let mut chain = Vec::new();
+                while !is_kept(current) {
+                    chain.push(current.clone());
+                    // A chain node has exactly two neighbours, so keep going through the other one
+                    let (next, weight) = self.adjacency_map[current]
+                        .iter()
+                        .find(|&(next, _)| next != previous)
+                        .expect("chain nodes have two neighbours");
+                    length += *weight;
+                    (previous, current) = (current, next);
+                }
 
-        Ok(std::iter::from_fn(move || {
-            while let Some(node) = nodes.pop() {
-                if !visited.contains(&node) {
-                    let component: Vec<_> = self.bfs(node).collect();
-                    // Remove nodes from this component
-                    nodes.retain(|n| !component.contains(n));
-                    // Add them to visited set
-                    visited.extend(component.iter().cloned());
-                    return Some(self.subgraph(&component));
+                let end = current;
+                if end == start || graph.get_weight(start, end).is_some_and(|&w| w <= length) {
+                    continue;
                 }
+                graph.add_edge_weighted(start.clone(), end.clone(), length);
+                let mut reversed = chain.clone();
+                reversed.reverse();
+                chains.insert((end.clone(), start.clone()), reversed);
+                chains.insert((start.clone(), end.clone()), chain);
+            }
+        }
+
+        Ok(ContractedGraph { graph, chains })
+    }
+
+    pub fn connected_components(&self) -> Result<impl Iterator<Item = Graph<N, W>> + '_, AocError> {
+        let labels = self.connected_component_labels()?;
+        let count = labels.values().max().map_or(0, |&label| label + 1);
+
+        // Number the components by their smallest node, so the output is deterministic
+        let mut smallest: Vec<Option<&N>> = vec![None; count];
+        for (node, &label) in &labels {
+            if smallest[label].is_none_or(|current| node < current) {
+                smallest[label] = Some(node);
             }
-            None
-        }))
+        }
+        let mut order: Vec<usize> = (0..count).collect();
+        order.sort_unstable_by_key(|&label| smallest[label]);
+        let mut position = vec![0; count];
+        for (index, &label) in order.iter().enumerate() {
+            position[label] = index;
+        }
+
+        let mut components: Vec<Graph<N, W>> = (0..count).map(|_| Graph::undirected()).collect();
+        for (node, &label) in &labels {
+            components[position[label]].add_node(node.clone());
+        }
+        for (from, to, weight) in self.edges() {
+            components[position[labels[from]]].add_edge_weighted(from.clone(), to.clone(), *weight);
+        }
+        Ok(components.into_iter())
+    }
+
+    /// Labels each node with its component, numbering the components `0..` in no particular
+    /// order. [`connected_components`](Self::connected_components) orders them by smallest node.
+    pub fn connected_component_labels(&self) -> Result<HashMap<N, usize>, AocError> {
+        if self.directed {
+            return Err(AocError::invalid_graph(
+                "Cannot find connected components of a directed graph; use weakly_connected_components or strongly_connected_components",
+            ));
+        }
+
+        let mut sets: UnionFind<&N> = self.adjacency_map.keys().collect();
+        for (from, to) in self.edge_pairs() {
+            sets.union(from, to);
+        }
+        Ok(sets
+            .labels()
+            .into_iter()
+            .map(|(node, label)| (node.clone(), label))
+            .collect())
     }
 
     pub fn shortest_path(&self, start: N, end: N) -> Option<(Vec<N>, W)>
@@ -179,70 +598,385 @@ where
     }
 }
 
+/// A graph with its corridors collapsed, as returned by [`Graph::contract_chains`].
+#[derive(Debug)]
+pub struct ContractedGraph<N, W>
+where
+    N: Eq + Hash + Ord,
+    W: Weight,
+{
+    /// The junctions, dead ends and pinned nodes, joined by edges weighted by chain length.
+    pub graph: Graph<N, W>,
+    // The original nodes strictly between the ends of each edge, in order, in both directions
+    chains: HashMap<(N, N), Vec<N>>,
+}
+
+impl<N, W> ContractedGraph<N, W>
+where
+    N: Eq + Hash + Clone + Ord,
+    W: Weight,
+{
+    /// The original nodes strictly between the ends of an edge of the contracted graph.
+    pub fn chain(&self, from: &N, to: &N) -> Option<&[N]> {
+        self.chains
+            .get(&(from.clone(), to.clone()))
+            .map(Vec::as_slice)
+    }
+
+    /// Expands a path through the contracted graph into the path through the original graph,
+    /// or `None` if consecutive nodes are not joined by an edge.
+    pub fn expand_path(&self, path: &[N]) -> Option<Vec<N>> {
+        let mut expanded: Vec<N> = path.first().cloned().into_iter().collect();
+        for pair in path.windows(2) {
+            expanded.extend_from_slice(self.chain(&pair[0], &pair[1])?);
+            expanded.push(pair[1].clone());
+        }
+        Some(expanded)
+    }
+}
+
+/// The DAG of strongly connected components returned by [`Graph::condensation`].
+#[derive(Debug)]
+pub struct Condensation<N, W>
+where
+    N: Eq + Hash + Ord,
+    W: Weight,
+{
+    /// One node per component, numbered in topological order. Parallel edges between two
+    /// components keep the smallest weight.
+    pub dag: Graph<usize, W>,
+    /// The nodes in each component, indexed by component id.
+    pub components: Vec<Vec<N>>,
+    pub component_of: HashMap<N, usize>,
+}
+
+/// A cycle found where an acyclic graph was needed, in edge order. The edge from the last node
+/// back to the first closes it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Cycle<N>(pub Vec<N>);
+
+impl<N: Debug> Display for Cycle<N> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for node in &self.0 {
+            write!(f, "{node:?} -> ")?;
+        }
+        match self.0.first() {
+            Some(first) => write!(f, "{first:?}"),
+            None => write!(f, "()"),
+        }
+    }
+}
+
+impl<N: Debug> From<Cycle<N>> for AocError {
+    fn from(cycle: Cycle<N>) -> Self {
+        AocError::invalid_graph(format!("Expected no cycles, found {cycle}"))
+    }
+}
+
+/// A graph given by the edges leaving each node, which the searches in this module run over.
+///
+/// [`Graph`] stores its edges up front, but any `Fn(&N) -> impl IntoIterator<Item = (N, W)>`
+/// closure is also a graph whose nodes are generated lazily, so state spaces too large to
+/// build, or infinite, can still be searched.
+pub trait Successors<N, W> {
+    /// The neighbours of `node`, with the weight of the edge to each.
+    fn successors(&self, node: &N) -> impl Iterator<Item = (N, W)>;
+}
+
+impl<N, W> Successors<N, W> for Graph<N, W>
+where
+    N: Eq + Hash + Clone + Ord,
+    W: Weight,
+{
+    fn successors(&self, node: &N) -> impl Iterator<Item = (N, W)> {
+        self.adjacency_map
+            .get(node)
+            .into_iter()
+            .flatten()
+            .map(|(next, weight)| (next.clone(), *weight))
+    }
+}
+
+impl<N, W, F, I> Successors<N, W> for F
+where
+    F: Fn(&N) -> I,
+    I: IntoIterator<Item = (N, W)>,
+{
+    fn successors(&self, node: &N) -> impl Iterator<Item = (N, W)> {
+        self(node).into_iter()
+    }
+}
+
 pub trait GraphTraversal<N> {
     fn next_node(&mut self) -> Option<N>;
 }
 
-pub struct Bfs<'a, N, W>
+pub struct Bfs<'a, N, W, G = Graph<N, W>>
 where
     N: Eq + Hash + Clone + Ord,
     W: Weight,
 {
-    graph: &'a Graph<N, W>,
+    graph: &'a G,
+    weight: PhantomData<W>,
     queue: VecDeque<N>,
-    visited: HashSet<N>,
+    // The source each discovered node was first reached from, doubling as the visited set
+    sources: HashMap<N, N>,
+    predecessors: HashMap<N, N>,
 }
 
-impl<'a, N, W> Bfs<'a, N, W>
+impl<'a, N, W, G> Bfs<'a, N, W, G>
 where
     N: Eq + Hash + Clone + Ord,
     W: Weight + Clone + Default,
+    G: Successors<N, W>,
 {
-    fn new(graph: &'a Graph<N, W>, start: N) -> Self {
-        let mut visited = HashSet::new();
+    pub fn new(graph: &'a G, start: N) -> Self {
+        Self::from_sources(graph, [start])
+    }
+
+    /// Searches from several sources at once, visiting nodes in order of their distance to the
+    /// nearest source. See [`source_of`](Self::source_of) to tell which source that was.
+    pub fn from_sources<I>(graph: &'a G, starts: I) -> Self
+    where
+        I: IntoIterator<Item = N>,
+    {
+        let mut sources = HashMap::new();
         let mut queue = VecDeque::new();
 
-        visited.insert(start.clone());
-        queue.push_back(start);
+        for start in starts {
+            if !sources.contains_key(&start) {
+                sources.insert(start.clone(), start.clone());
+                queue.push_back(start);
+            }
+        }
 
         Self {
             graph,
+            weight: PhantomData,
             queue,
-            visited,
+            sources,
+            predecessors: HashMap::new(),
         }
     }
+
+    /// Continues the search until a node matching `is_goal` is visited, and returns a path
+    /// with the fewest edges from a source to it.
+    pub fn shortest_path_where<F>(&mut self, is_goal: F) -> Option<Vec<N>>
+    where
+        F: Fn(&N) -> bool,
+    {
+        let goal = self.find(|node| is_goal(node))?;
+        Some(reconstruct_path(&self.predecessors, &goal))
+    }
+
+    /// The source that first reached `node`, once it has been discovered.
+    pub fn source_of(&self, node: &N) -> Option<&N> {
+        self.sources.get(node)
+    }
+
+    /// Yields each visited node along with the source that reached it first, partitioning the
+    /// reachable nodes into regions around their nearest source.
+    pub fn tagged(mut self) -> impl Iterator<Item = (N, N)> + 'a
+    where
+        N: 'a,
+        W: 'a,
+    {
+        std::iter::from_fn(move || {
+            let node = self.next()?;
+            let source = self.sources[&node].clone();
+            Some((node, source))
+        })
+    }
 }
 
-impl<'a, N, W> Iterator for Bfs<'a, N, W>
+impl<'a, N, W, G> Iterator for Bfs<'a, N, W, G>
 where
     N: Eq + Hash + Clone + Ord,
     W: Weight + Clone + Default,
+    G: Successors<N, W>,
 {
     type Item = N;
 
     fn next(&mut self) -> Option<Self::Item> {
         let current = self.queue.pop_front()?;
-        self.visited.insert(current.clone());
-        if let Some(neighbors) = self.graph.neighbors(&current) {
-            for neighbor in neighbors {
-                if !self.visited.contains(&neighbor) {
-                    self.visited.insert(neighbor.clone());
-                    self.queue.push_back(neighbor.clone());
-                }
+        let source = self.sources[&current].clone();
+        for (neighbor, _) in self.graph.successors(&current) {
+            if !self.sources.contains_key(&neighbor) {
+                self.sources.insert(neighbor.clone(), source.clone());
+                self.predecessors.insert(neighbor.clone(), current.clone());
+                self.queue.push_back(neighbor);
             }
         }
         Some(current)
     }
 }
 
-pub struct Dijkstra<'a, N, W>
+impl<'a, N, W, G> GraphTraversal<N> for Bfs<'a, N, W, G>
 where
     N: Eq + Hash + Clone + Ord,
     W: Weight + Clone + Default,
+    G: Successors<N, W>,
 {
-    graph: &'a Graph<N, W>,
+    fn next_node(&mut self) -> Option<N> {
+        self.next()
+    }
+}
+
+/// How a depth-first search reached the target of an edge.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum EdgeKind {
+    /// The target was undiscovered, so the search descends into it.
+    Tree,
+    /// The target is an ancestor still being explored, so the edge closes a cycle.
+    Back,
+    /// The target is an already finished descendant.
+    Forward,
+    /// The target was finished in an earlier branch or an earlier search tree.
+    Cross,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum DfsEvent<N> {
+    /// First visit to a node, i.e. pre-order.
+    Enter(N),
+    /// All of a node's descendants are finished, i.e. post-order.
+    Exit(N),
+    Edge {
+        from: N,
+        to: N,
+        kind: EdgeKind,
+    },
+}
+
+/// Iterative depth-first search yielding [`DfsEvent`]s, so it works on graphs deeper than the
+/// call stack.
+///
+/// Successors are visited in ascending order so traversals are reproducible. In an undirected
+/// graph every edge is seen from both ends, so the edge back to a node's parent shows up as
+/// [`EdgeKind::Back`].
+pub struct Dfs<'a, N, W, G = Graph<N, W>>
+where
+    N: Eq + Hash + Clone + Ord,
+    W: Weight,
+{
+    graph: &'a G,
+    weight: PhantomData<W>,
+    roots: VecDeque<N>,
+    // Each node on the current path with the successors it has yet to try
+    stack: Vec<(N, std::vec::IntoIter<N>)>,
+    discovered: HashMap<N, usize>,
+    finished: HashSet<N>,
+    // The `Enter` that follows a tree edge
+    pending: Option<DfsEvent<N>>,
+}
+
+impl<'a, N, W, G> Dfs<'a, N, W, G>
+where
+    N: Eq + Hash + Clone + Ord,
+    W: Weight,
+    G: Successors<N, W>,
+{
+    pub fn new(graph: &'a G, start: N) -> Self {
+        Self::from_roots(graph, [start])
+    }
+
+    /// Searches from each of `roots` in turn, skipping any reached by an earlier one.
+    pub fn from_roots<I>(graph: &'a G, roots: I) -> Self
+    where
+        I: IntoIterator<Item = N>,
+    {
+        Self {
+            graph,
+            weight: PhantomData,
+            roots: roots.into_iter().collect(),
+            stack: Vec::new(),
+            discovered: HashMap::new(),
+            finished: HashSet::new(),
+            pending: None,
+        }
+    }
+
+    fn enter(&mut self, node: N) -> DfsEvent<N> {
+        let successors: Vec<N> = self
+            .graph
+            .successors(&node)
+            .map(|(next, _)| next)
+            .sorted()
+            .collect();
+        self.discovered.insert(node.clone(), self.discovered.len());
+        self.stack.push((node.clone(), successors.into_iter()));
+        DfsEvent::Enter(node)
+    }
+}
+
+impl<'a, N, W, G> Iterator for Dfs<'a, N, W, G>
+where
+    N: Eq + Hash + Clone + Ord,
+    W: Weight,
+    G: Successors<N, W>,
+{
+    type Item = DfsEvent<N>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Some(event) = self.pending.take() {
+            return Some(event);
+        }
+
+        let Some((node, successors)) = self.stack.last_mut() else {
+            // Start a new search tree from the next unvisited root
+            let root = std::iter::from_fn(|| self.roots.pop_front())
+                .find(|root| !self.discovered.contains_key(root))?;
+            return Some(self.enter(root));
+        };
+
+        let Some(next) = successors.next() else {
+            let (node, _) = self.stack.pop()?;
+            self.finished.insert(node.clone());
+            return Some(DfsEvent::Exit(node));
+        };
+
+        let from = node.clone();
+        let kind = match self.discovered.get(&next) {
+            None => EdgeKind::Tree,
+            Some(_) if !self.finished.contains(&next) => EdgeKind::Back,
+            Some(&order) if order > self.discovered[&from] => EdgeKind::Forward,
+            Some(_) => EdgeKind::Cross,
+        };
+        if kind == EdgeKind::Tree {
+            self.pending = Some(self.enter(next.clone()));
+        }
+        Some(DfsEvent::Edge {
+            from,
+            to: next,
+            kind,
+        })
+    }
+}
+
+impl<'a, N, W, G> GraphTraversal<N> for Dfs<'a, N, W, G>
+where
+    N: Eq + Hash + Clone + Ord,
+    W: Weight,
+    G: Successors<N, W>,
+{
+    /// The next node in pre-order.
+    fn next_node(&mut self) -> Option<N> {
+        self.find_map(|event| match event {
+            DfsEvent::Enter(node) => Some(node),
+            _ => None,
+        })
+    }
+}
+
+pub struct Dijkstra<'a, N, W, G = Graph<N, W>>
+where
+    N: Eq + Hash + Clone + Ord,
+    W: Weight + Clone + Default,
+{
+    graph: &'a G,
     distances: HashMap<N, W>,
     predecessors: HashMap<N, N>,
+    sources: HashMap<N, N>,
     queue: BinaryHeap<State<N, W>>,
 }
 
@@ -270,33 +1004,59 @@ impl<N: Ord, W: PartialOrd + Eq> PartialOrd for State<N, W> {
     }
 }
 
-impl<'a, N, W> Dijkstra<'a, N, W>
+impl<'a, N, W, G> Dijkstra<'a, N, W, G>
 where
     N: Eq + Hash + Clone + Ord,
     W: Weight + Clone + Default + Eq,
+    G: Successors<N, W>,
 {
-    pub fn new(graph: &'a Graph<N, W>, start: N) -> Self {
+    /// Debug builds panic if the search meets a negative edge weight, which would make its
+    /// answers wrong; use [`BellmanFord`] for those.
+    pub fn new(graph: &'a G, start: N) -> Self {
+        Self::from_sources(graph, [start])
+    }
+
+    /// Searches from several sources at once, so each node's distance is to its nearest source.
+    pub fn from_sources<I>(graph: &'a G, sources: I) -> Self
+    where
+        I: IntoIterator<Item = N>,
+    {
         let mut dijkstra = Self {
             graph,
             distances: HashMap::new(),
             predecessors: HashMap::new(),
+            sources: HashMap::new(),
             queue: BinaryHeap::new(),
         };
 
-        // Initialize start node
-        dijkstra.distances.insert(start.clone(), W::zero());
-        dijkstra.queue.push(State {
-            node: start,
-            distance: W::zero(),
-        });
+        for source in sources {
+            dijkstra.distances.insert(source.clone(), W::zero());
+            dijkstra.sources.insert(source.clone(), source.clone());
+            dijkstra.queue.push(State {
+                node: source,
+                distance: W::zero(),
+            });
+        }
 
         dijkstra
     }
 
     pub fn shortest_path(&mut self, end: &N) -> Option<(Vec<N>, W)> {
+        self.shortest_path_where(|node| node == end)
+    }
+
+    /// A shortest path to the nearest node matching `is_goal`, so goals need not be a single
+    /// node, e.g. a position reached with any facing.
+    pub fn shortest_path_where<F>(&mut self, is_goal: F) -> Option<(Vec<N>, W)>
+    where
+        F: Fn(&N) -> bool,
+    {
         while let Some(State { node, distance }) = self.queue.pop() {
-            if &node == end {
-                return Some((self.reconstruct_path(end), distance));
+            if is_goal(&node) {
+                let path = self.reconstruct_path(&node);
+                // Leave it queued so a later search can resume past it
+                self.queue.push(State { node, distance });
+                return Some((path, distance));
             }
 
             if let Some(best) = self.distances.get(&node) {
@@ -305,121 +1065,593 @@ where
                 }
             }
 
-            if let Some(neighbors) = self.graph.neighbors_weighted(&node) {
-                for (next, weight) in neighbors {
-                    let mut next_distance = distance.clone();
-                    next_distance += weight.clone();
+            self.relax(&node, distance);
+        }
+        None
+    }
 
-                    if !self.distances.contains_key(next) || next_distance < self.distances[next] {
-                        self.distances.insert(next.clone(), next_distance.clone());
-                        self.predecessors.insert(next.clone(), node.clone());
-                        self.queue.push(State {
-                            node: next.clone(),
-                            distance: next_distance,
-                        });
-                    }
-                }
+    /// Settles every node reachable from the start, including any not yet reached by earlier
+    /// calls to [`shortest_path`](Self::shortest_path).
+    pub fn run_to_completion(mut self) -> ShortestPathTree<N, W> {
+        while let Some(State { node, distance }) = self.queue.pop() {
+            if distance > self.distances[&node] {
+                continue;
+            }
+            self.relax(&node, distance);
+        }
+
+        ShortestPathTree {
+            distances: self.distances,
+            predecessors: self.predecessors,
+            sources: self.sources,
+        }
+    }
+
+    fn relax(&mut self, node: &N, distance: W) {
+        for (next, weight) in self.graph.successors(node) {
+            let mut next_distance = distance;
+            next_distance += non_negative(weight);
+
+            if !self.distances.contains_key(&next) || next_distance < self.distances[&next] {
+                let source = self.sources[node].clone();
+                self.distances.insert(next.clone(), next_distance);
+                self.predecessors.insert(next.clone(), node.clone());
+                self.sources.insert(next.clone(), source);
+                self.queue.push(State {
+                    node: next,
+                    distance: next_distance,
+                });
             }
         }
-        None
     }
 
     fn reconstruct_path(&self, end: &N) -> Vec<N> {
-        let mut path = vec![end.clone()];
-        let mut current = end;
+        reconstruct_path(&self.predecessors, end)
+    }
+}
+
+/// Dijkstra and A* never revisit a settled node, which is only correct if no edge can make a
+/// route shorter.
+pub(crate) fn non_negative<W: Weight>(weight: W) -> W {
+    debug_assert!(
+        weight >= W::zero(),
+        "Negative edge weight {weight}; use BellmanFord instead"
+    );
+    weight
+}
+
+/// Bellman-Ford shortest paths, which unlike [`Dijkstra`] handles negative edge weights, and
+/// reports a negative cycle if one is reachable, since then some distances have no minimum.
+pub struct BellmanFord<'a, N, W>
+where
+    N: Eq + Hash + Clone + Ord,
+    W: Weight,
+{
+    graph: &'a Graph<N, W>,
+    sources: Vec<N>,
+}
+
+impl<'a, N, W> BellmanFord<'a, N, W>
+where
+    N: Eq + Hash + Clone + Ord,
+    W: Weight + Clone + Default,
+{
+    pub fn new(graph: &'a Graph<N, W>, start: N) -> Self {
+        Self::from_sources(graph, [start])
+    }
+
+    pub fn from_sources<I>(graph: &'a Graph<N, W>, sources: I) -> Self
+    where
+        I: IntoIterator<Item = N>,
+    {
+        Self {
+            graph,
+            sources: sources.into_iter().collect(),
+        }
+    }
+
+    /// Distances to every reachable node, or a negative cycle reachable from a source. Takes
+    /// `O(nodes * edges)` time.
+    pub fn run(self) -> Result<ShortestPathTree<N, W>, Cycle<N>> {
+        let mut distances: HashMap<N, W> = HashMap::new();
+        let mut predecessors: HashMap<N, N> = HashMap::new();
+        let mut sources: HashMap<N, N> = HashMap::new();
+        for source in &self.sources {
+            distances.insert(source.clone(), W::zero());
+            sources.insert(source.clone(), source.clone());
+        }
+
+        // Shortest paths have fewer edges than there are nodes, so once every edge has been
+        // relaxed that many times, any further improvement must come from a negative cycle
+        let node_count = self.graph.nodes().len();
+        for round in 0..=node_count {
+            let mut last_improved = None;
+            for (from, to, &weight) in self.graph.edges() {
+                let Some(&distance) = distances.get(from) else {
+                    continue;
+                };
+                let mut next_distance = distance;
+                next_distance += weight;
+
+                if distances.get(to).is_none_or(|&best| next_distance < best) {
+                    distances.insert(to.clone(), next_distance);
+                    predecessors.insert(to.clone(), from.clone());
+                    let source = sources[from].clone();
+                    sources.insert(to.clone(), source);
+                    last_improved = Some(to);
+                }
+            }
 
-        while let Some(predecessor) = self.predecessors.get(current) {
-            path.push(predecessor.clone());
-            current = predecessor;
+            match last_improved {
+                None => break,
+                Some(node) if round == node_count => {
+                    return Err(negative_cycle(&predecessors, node, node_count));
+                }
+                Some(_) => {}
+            }
         }
 
-        path.reverse();
-        path
+        Ok(ShortestPathTree {
+            distances,
+            predecessors,
+            sources,
+        })
+    }
+}
+
+/// Reads a cycle off the predecessors of `node`, which was improved after the distances
+/// should have settled.
+fn negative_cycle<N: Eq + Hash + Clone + Ord>(
+    predecessors: &HashMap<N, N>,
+    node: &N,
+    node_count: usize,
+) -> Cycle<N> {
+    // Walking back once per node is enough to be sure of standing on the cycle itself
+    let mut on_cycle = node;
+    for _ in 0..node_count {
+        on_cycle = &predecessors[on_cycle];
+    }
+
+    let mut cycle = vec![on_cycle.clone()];
+    let mut current = &predecessors[on_cycle];
+    while current != on_cycle {
+        cycle.push(current.clone());
+        current = &predecessors[current];
+    }
+
+    // Predecessors run backwards; list in edge order starting from the smallest node
+    cycle.reverse();
+    let smallest = cycle.iter().position_min().unwrap_or(0);
+    cycle.rotate_left(smallest);
+    Cycle(cycle)
+}
+
+/// Shortest distances from the start, or the nearest of several sources, to every node
+/// reachable from it, as returned by [`Dijkstra::run_to_completion`].
+#[derive(Clone, Debug)]
+pub struct ShortestPathTree<N, W> {
+    distances: HashMap<N, W>,
+    predecessors: HashMap<N, N>,
+    sources: HashMap<N, N>,
+}
+
+impl<N, W> ShortestPathTree<N, W>
+where
+    N: Eq + Hash + Clone,
+    W: Copy,
+{
+    pub fn distance_to(&self, node: &N) -> Option<W> {
+        self.distances.get(node).copied()
+    }
+
+    /// The source nearest to `node`, i.e. the first node of [`path_to`](Self::path_to).
+    pub fn source_of(&self, node: &N) -> Option<&N> {
+        self.sources.get(node)
+    }
+
+    /// A shortest path from the start to `node`, inclusive, or `None` if it is unreachable.
+    pub fn path_to(&self, node: &N) -> Option<Vec<N>> {
+        self.distances
+            .contains_key(node)
+            .then(|| reconstruct_path(&self.predecessors, node))
+    }
+
+    pub fn contains(&self, node: &N) -> bool {
+        self.distances.contains_key(node)
+    }
+
+    /// Number of reached nodes, including the start.
+    pub fn len(&self) -> usize {
+        self.distances.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.distances.is_empty()
+    }
+
+    /// Every reached node with its distance, in arbitrary order.
+    pub fn iter(&self) -> impl Iterator<Item = (&N, W)> {
+        self.distances
+            .iter()
+            .map(|(node, &distance)| (node, distance))
+    }
+}
+
+fn reconstruct_path<N: Eq + Hash + Clone>(predecessors: &HashMap<N, N>, end: &N) -> Vec<N> {
+    let mut path = vec![end.clone()];
+    let mut current = end;
+
+    while let Some(predecessor) = predecessors.get(current) {
+        path.push(predecessor.clone());
+        current = predecessor;
     }
+
+    path.reverse();
+    path
+}
+
+/// A* search: Dijkstra guided by a `heuristic` estimating the remaining distance to the goal.
+///
+/// The result is a shortest path as long as the heuristic is admissible, i.e. never
+/// overestimates. A heuristic of zero everywhere behaves exactly like [`Dijkstra`].
+pub struct AStar<'a, N, W, H, G = Graph<N, W>>
+where
+    N: Eq + Hash + Clone + Ord,
+    W: Weight + Clone + Default,
+    H: Fn(&N) -> W,
+{
+    graph: &'a G,
+    heuristic: H,
+    distances: HashMap<N, W>,
+    predecessors: HashMap<N, N>,
+    // Ordered by estimated total distance, i.e. distance so far plus heuristic
+    queue: BinaryHeap<State<N, W>>,
+    expanded: usize,
 }
 
-impl<'a, N, W> Dijkstra<'a, N, W>
+impl<'a, N, W, H, G> AStar<'a, N, W, H, G>
 where
     N: Eq + Hash + Clone + Ord,
     W: Weight + Clone + Default + Eq,
+    H: Fn(&N) -> W,
+    G: Successors<N, W>,
 {
-    pub fn all_shortest_paths(&mut self, end: &N) -> Option<(Vec<Vec<N>>, W)> {
-        // Track all predecessors for each node
-        let mut all_predecessors: HashMap<N, Vec<N>> = HashMap::new();
+    pub fn new(graph: &'a G, start: N, heuristic: H) -> Self {
+        let estimate = heuristic(&start);
+        let mut astar = Self {
+            graph,
+            heuristic,
+            distances: HashMap::new(),
+            predecessors: HashMap::new(),
+            queue: BinaryHeap::new(),
+            expanded: 0,
+        };
+
+        astar.distances.insert(start.clone(), W::zero());
+        astar.queue.push(State {
+            node: start,
+            distance: estimate,
+        });
 
+        astar
+    }
+
+    /// Number of nodes whose neighbours have been explored so far.
+    pub fn expanded(&self) -> usize {
+        self.expanded
+    }
+
+    pub fn shortest_path(&mut self, end: &N) -> Option<(Vec<N>, W)> {
         while let Some(State { node, distance }) = self.queue.pop() {
+            let best = self.distances[&node];
             if &node == end {
-                return Some((self.reconstruct_all_paths(end, &all_predecessors), distance));
+                let path = reconstruct_path(&self.predecessors, end);
+                // Leave it queued so a later search can resume past it
+                self.queue.push(State { node, distance });
+                return Some((path, best));
             }
 
-            if let Some(best) = self.distances.get(&node) {
-                if distance > *best {
-                    continue;
+            // Skip stale entries superseded by a shorter route
+            if distance > best + (self.heuristic)(&node) {
+                continue;
+            }
+            self.expanded += 1;
+
+            for (next, weight) in self.graph.successors(&node) {
+                let mut next_distance = best;
+                next_distance += non_negative(weight);
+
+                if !self.distances.contains_key(&next) || next_distance < self.distances[&next] {
+                    let estimate = next_distance + (self.heuristic)(&next);
+                    self.distances.insert(next.clone(), next_distance);
+                    self.predecessors.insert(next.clone(), node.clone());
+                    self.queue.push(State {
+                        node: next,
+                        distance: estimate,
+                    });
+                }
+            }
+        }
+        None
+    }
+}
+
+impl<'a, N, W, G> Dijkstra<'a, N, W, G>
+where
+    N: Eq + Hash + Clone + Ord,
+    W: Weight + Clone + Default + Eq,
+    G: Successors<N, W>,
+{
+    /// Every shortest path to `end`, or a zero-weight cycle on them if there are infinitely
+    /// many. The number of paths can grow exponentially with the size of the graph, so prefer
+    /// [`shortest_path_dag`](Self::shortest_path_dag) where possible.
+    pub fn all_shortest_paths(&self, end: &N) -> Result<Option<AllPaths<N, W>>, Cycle<N>> {
+        self.all_shortest_paths_where(|node| node == end)
+    }
+
+    /// Every shortest path to the nearest nodes matching `is_goal`.
+    pub fn all_shortest_paths_where<F>(
+        &self,
+        is_goal: F,
+    ) -> Result<Option<AllPaths<N, W>>, Cycle<N>>
+    where
+        F: Fn(&N) -> bool,
+    {
+        let Some(dag) = self.shortest_path_dag_where(is_goal) else {
+            return Ok(None);
+        };
+        Ok(Some((dag.paths()?, dag.distance())))
+    }
+
+    /// The union of all shortest paths to `end`, as a DAG of predecessors.
+    pub fn shortest_path_dag(&self, end: &N) -> Option<ShortestPathDag<N, W>> {
+        self.shortest_path_dag_where(|node| node == end)
+    }
+
+    /// The union of all shortest paths to whichever of `goals` are nearest.
+    pub fn shortest_path_dag_to_any(&self, goals: &HashSet<N>) -> Option<ShortestPathDag<N, W>> {
+        self.shortest_path_dag_where(|node| goals.contains(node))
+    }
+
+    /// The union of all shortest paths to the nearest nodes matching `is_goal`.
+    ///
+    /// Every node at the goal distance is settled before returning, so ties reached through
+    /// zero-weight edges are included, even where they form a cycle. Paths start from the last
+    /// source they pass. This always searches afresh from the sources, since earlier searches
+    /// only kept one predecessor per node.
+    pub fn shortest_path_dag_where<F>(&self, is_goal: F) -> Option<ShortestPathDag<N, W>>
+    where
+        F: Fn(&N) -> bool,
+    {
+        let sources: HashSet<&N> = self
+            .sources
+            .iter()
+            .filter(|(node, source)| node == source)
+            .map(|(node, _)| node)
+            .collect();
+        let mut distances: HashMap<N, W> = sources
+            .iter()
+            .map(|&source| (source.clone(), W::zero()))
+            .collect();
+        let mut queue: BinaryHeap<State<N, W>> = sources
+            .iter()
+            .map(|&source| State {
+                node: source.clone(),
+                distance: W::zero(),
+            })
+            .collect();
+
+        // Track all predecessors for each node
+        let mut all_predecessors: HashMap<N, Vec<N>> = HashMap::new();
+        let mut settled = HashSet::new();
+        let mut ends = Vec::new();
+        let mut goal_distance = None;
+
+        while let Some(State { node, distance }) = queue.pop() {
+            if goal_distance.is_some_and(|goal| distance > goal) {
+                break;
+            }
+
+            // Skip stale entries, and never expand a node twice
+            if distance > distances[&node] || !settled.insert(node.clone()) {
+                continue;
+            }
+
+            if is_goal(&node) {
+                goal_distance = Some(distance);
+                ends.push(node.clone());
+            }
+
+            for (next, weight) in self.graph.successors(&node) {
+                let mut next_distance = distance;
+                next_distance += non_negative(weight);
+
+                match distances.get(&next) {
+                    Some(current_best) if next_distance > *current_best => continue,
+                    Some(current_best) if next_distance == *current_best => {
+                        // Found another path with same distance
+                        if sources.contains(&next) {
+                            continue;
+                        }
+                        all_predecessors.entry(next).or_default().push(node.clone());
+                    }
+                    _ => {
+                        // Found better path
+                        distances.insert(next.clone(), next_distance);
+                        all_predecessors.insert(next.clone(), vec![node.clone()]);
+                        queue.push(State {
+                            node: next,
+                            distance: next_distance,
+                        });
+                    }
                 }
             }
+        }
+
+        goal_distance.map(|distance| ShortestPathDag {
+            ends,
+            distance,
+            predecessors: all_predecessors,
+        })
+    }
+}
+
+/// Every shortest path, with their shared distance.
+pub type AllPaths<N, W> = (Vec<Vec<N>>, W);
+
+/// All shortest paths from the sources to the nearest end nodes, as each node's predecessors
+/// on those paths. Queries take time linear in the size of the DAG, not the number of paths.
+///
+/// Zero-weight edges can close a cycle, which makes the number of shortest paths infinite;
+/// the queries that count or list paths then report that cycle instead.
+#[derive(Clone, Debug)]
+pub struct ShortestPathDag<N, W> {
+    ends: Vec<N>,
+    distance: W,
+    // May include nodes that do not lead to an end; queries walk back from the ends to skip them
+    predecessors: HashMap<N, Vec<N>>,
+}
+
+impl<N, W> ShortestPathDag<N, W>
+where
+    N: Eq + Hash + Clone,
+    W: Copy,
+{
+    /// Every goal node at the shortest distance, in the order they were settled.
+    pub fn ends(&self) -> &[N] {
+        &self.ends
+    }
 
-            if let Some(neighbors) = self.graph.neighbors_weighted(&node) {
-                for (next, weight) in neighbors {
-                    let mut next_distance = distance.clone();
-                    next_distance += weight.clone();
+    /// The length of every path in the DAG.
+    pub fn distance(&self) -> W {
+        self.distance
+    }
+
+    fn predecessors_of(&self, node: &N) -> &[N] {
+        self.predecessors.get(node).map_or(&[], Vec::as_slice)
+    }
+
+    /// Nodes on any shortest path, each after all of its predecessors, or a cycle among them.
+    fn topological_order(&self) -> Result<Vec<&N>, Cycle<N>> {
+        let mut order = Vec::new();
+        let mut visited = HashSet::new();
 
-                    match self.distances.get(next) {
-                        Some(current_best) if next_distance > *current_best => continue,
-                        Some(current_best) if next_distance == *current_best => {
-                            // Found another path with same distance
-                            all_predecessors
-                                .entry(next.clone())
-                                .or_default()
-                                .push(node.clone());
+        for end in &self.ends {
+            if !visited.insert(end) {
+                continue;
+            }
+            // Depth-first over predecessors, emitting a node once all of its predecessors are
+            // done. The stack holds the current path, so meeting a node on it closes a cycle.
+            let mut stack = vec![(end, 0)];
+            let mut on_stack = HashSet::from([end]);
+            while let Some((node, next)) = stack.pop() {
+                match self.predecessors_of(node).get(next) {
+                    Some(predecessor) => {
+                        stack.push((node, next + 1));
+                        if on_stack.contains(predecessor) {
+                            let start = stack.iter().position(|&(n, _)| n == predecessor);
+                            // The stack runs against the edges, so reverse it into edge order
+                            let cycle = stack[start.unwrap_or(0)..]
+                                .iter()
+                                .rev()
+                                .map(|&(n, _)| n.clone());
+                            return Err(Cycle(cycle.collect()));
                         }
-                        _ => {
-                            // Found better path
-                            self.distances.insert(next.clone(), next_distance.clone());
-                            all_predecessors.entry(next.clone()).or_default().clear();
-                            all_predecessors
-                                .entry(next.clone())
-                                .or_default()
-                                .push(node.clone());
-                            self.queue.push(State {
-                                node: next.clone(),
-                                distance: next_distance,
-                            });
+                        if visited.insert(predecessor) {
+                            on_stack.insert(predecessor);
+                            stack.push((predecessor, 0));
                         }
                     }
+                    None => {
+                        on_stack.remove(node);
+                        order.push(node);
+                    }
                 }
             }
         }
-        None
+        Ok(order)
+    }
+
+    /// Nodes that lead to an end through predecessors, i.e. those on any shortest path.
+    fn on_any_path(&self) -> HashSet<&N> {
+        let mut seen: HashSet<&N> = self.ends.iter().collect();
+        let mut stack: Vec<&N> = seen.iter().copied().collect();
+        while let Some(node) = stack.pop() {
+            for predecessor in self.predecessors_of(node) {
+                if seen.insert(predecessor) {
+                    stack.push(predecessor);
+                }
+            }
+        }
+        seen
+    }
+
+    /// The number of distinct shortest paths, counted in `C` so that callers expecting huge
+    /// counts can pick a wide or arbitrary-precision integer.
+    pub fn count_paths<C>(&self) -> Result<C, Cycle<N>>
+    where
+        C: Clone + Zero + One,
+    {
+        let mut counts: HashMap<&N, C> = HashMap::new();
+        for node in self.topological_order()? {
+            let predecessors = self.predecessors_of(node);
+            let count = if predecessors.is_empty() {
+                C::one()
+            } else {
+                predecessors
+                    .iter()
+                    .fold(C::zero(), |total, p| total + counts[p].clone())
+            };
+            counts.insert(node, count);
+        }
+        Ok(self
+            .ends
+            .iter()
+            .fold(C::zero(), |total, end| total + counts[end].clone()))
+    }
+
+    pub fn nodes_on_any_path(&self) -> HashSet<N> {
+        self.on_any_path().into_iter().cloned().collect()
+    }
+
+    /// The `(from, to)` edges used by any shortest path.
+    pub fn edges_on_any_path(&self) -> HashSet<(N, N)> {
+        self.on_any_path()
+            .into_iter()
+            .flat_map(|to| {
+                self.predecessors_of(to)
+                    .iter()
+                    .map(move |from| (from.clone(), to.clone()))
+            })
+            .collect()
     }
 
-    fn reconstruct_all_paths(&self, end: &N, all_predecessors: &HashMap<N, Vec<N>>) -> Vec<Vec<N>> {
+    /// Enumerates every shortest path. Beware that there can be exponentially many.
+    pub fn paths(&self) -> Result<Vec<Vec<N>>, Cycle<N>> {
+        self.topological_order()?;
         let mut all_paths = Vec::new();
         let mut current_path = Vec::new();
 
-        self.build_paths(end, all_predecessors, &mut current_path, &mut all_paths);
+        for end in &self.ends {
+            self.build_paths(end, &mut current_path, &mut all_paths);
+        }
 
         // Reverse each path since we built them backwards
         all_paths.iter_mut().for_each(|path| path.reverse());
-        all_paths
+        Ok(all_paths)
     }
 
-    fn build_paths(
-        &self,
-        current: &N,
-        all_predecessors: &HashMap<N, Vec<N>>,
-        current_path: &mut Vec<N>,
-        all_paths: &mut Vec<Vec<N>>,
-    ) {
+    fn build_paths(&self, current: &N, current_path: &mut Vec<N>, all_paths: &mut Vec<Vec<N>>) {
         current_path.push(current.clone());
 
-        if let Some(predecessors) = all_predecessors.get(current) {
-            for predecessor in predecessors {
-                self.build_paths(predecessor, all_predecessors, current_path, all_paths);
-            }
-        } else {
+        let predecessors = self.predecessors_of(current);
+        if predecessors.is_empty() {
             // Reached the start node
             all_paths.push(current_path.clone());
         }
+        for predecessor in predecessors {
+            self.build_paths(predecessor, current_path, all_paths);
+        }
 
         current_path.pop();
     }
@@ -508,6 +1740,46 @@ mod tests {
         assert_eq!(path, vec![1, 2]);
     }
 
+    #[test]
+    fn test_bfs_from_sources() {
+        let mut graph: Graph<i32> = Graph::undirected();
+        // 1 - 2 - 3 - 4 - 5 - 6, plus 7 hanging off 6
+        for (from, to) in [(1, 2), (2, 3), (3, 4), (4, 5), (5, 6), (6, 7)] {
+            graph.add_edge(from, to);
+        }
+
+        let path: Vec<i32> = graph.bfs_from([1, 6]).collect();
+        assert_eq!(path.len(), 7);
+        assert_eq!(&path[..2], &[1, 6]);
+        assert!(path[2..5].contains(&2));
+        assert!(path[2..5].contains(&5));
+        assert!(path[2..5].contains(&7));
+
+        let mut tagged: Vec<(i32, i32)> = graph.bfs_from([1, 6]).tagged().collect();
+        tagged.sort();
+        assert_eq!(
+            tagged,
+            vec![(1, 1), (2, 1), (3, 1), (4, 6), (5, 6), (6, 6), (7, 6)]
+        );
+    }
+
+    #[test]
+    fn test_bfs_shortest_path_where() {
+        let mut graph: Graph<(i32, char)> = Graph::undirected();
+        graph.add_edge((1, 'a'), (2, 'a'));
+        graph.add_edge((2, 'a'), (3, 'a'));
+        graph.add_edge((3, 'a'), (4, 'b'));
+        graph.add_edge((1, 'a'), (4, 'c'));
+
+        let mut bfs = graph.bfs((1, 'a'));
+        let path = bfs.shortest_path_where(|&(id, _)| id == 4).unwrap();
+        assert_eq!(path, vec![(1, 'a'), (4, 'c')]);
+        // The search resumes from where it stopped
+        let path = bfs.shortest_path_where(|&(_, c)| c == 'b').unwrap();
+        assert_eq!(path.len(), 4);
+        assert_eq!(bfs.shortest_path_where(|&(id, _)| id == 5), None);
+    }
+
     #[test]
     fn test_string_bfs() {
         let mut graph: Graph<char> = Graph::directed();
@@ -589,6 +1861,57 @@ mod tests {
         assert!(components[1].neighbors(&4).unwrap().contains(&5));
     }
 
+    #[test]
+    fn test_connected_component_labels() {
+        let mut graph: Graph<i32> = Graph::undirected();
+        graph.add_edge(5, 4);
+        graph.add_edge(2, 1);
+        graph.add_edge(3, 2);
+        graph.add_node(0);
+
+        let labels = graph.connected_component_labels().unwrap();
+        assert_eq!(labels.len(), 6);
+        assert_eq!((labels[&2], labels[&3]), (labels[&1], labels[&1]));
+        assert_eq!(labels[&5], labels[&4]);
+        let distinct: HashSet<usize> = [labels[&0], labels[&1], labels[&4]].into();
+        assert_eq!(distinct, HashSet::from([0, 1, 2]));
+        assert_eq!(graph.connected_components().unwrap().count(), 3);
+    }
+
+    #[test]
+    fn test_connected_components_large() {
+        // A 100 x 100 grid of 4 x 4 blocks, big enough that a quadratic search would crawl
+        let mut graph: Graph<(u32, u32)> = Graph::undirected();
+        for x in 0..100 {
+            for y in 0..100 {
+                graph.add_node((x, y));
+                if x % 4 != 3 {
+                    graph.add_edge((x, y), (x + 1, y));
+                }
+                if y % 4 != 3 {
+                    graph.add_edge((x, y), (x, y + 1));
+                }
+            }
+        }
+
+        let components: Vec<_> = graph.connected_components().unwrap().collect();
+        assert_eq!(components.len(), 25 * 25);
+        assert!(components.iter().all(|c| c.nodes().len() == 16));
+        assert_eq!(components[1].nodes()[0], &(0, 4));
+    }
+
+    #[test]
+    fn test_connected_components_directed() {
+        let mut graph: Graph<i32> = Graph::directed();
+        graph.add_edge(1, 2);
+
+        assert!(matches!(
+            graph.connected_components(),
+            Err(AocError::InvalidGraph(_))
+        ));
+        assert!(graph.connected_component_labels().is_err());
+    }
+
     #[test]
     fn test_bfs_weighted_graph() {
         let mut graph: Graph<i32, f64> = Graph::directed();
@@ -840,26 +2163,101 @@ mod dijkstra_tests {
 }
 
 #[cfg(test)]
-mod all_shortest_paths_tests {
+mod shortest_path_tree_tests {
     use super::*;
 
+    fn complex_graph() -> Graph<i32, usize> {
+        let mut graph = Graph::directed();
+        let edges = [
+            (1, 2, 4),
+            (2, 3, 3),
+            (1, 4, 8),
+            (2, 5, 2),
+            (3, 6, 5),
+            (4, 5, 3),
+            (5, 6, 2),
+        ];
+        for (from, to, weight) in edges {
+            graph.add_edge_weighted(from, to, weight);
+        }
+        graph.add_edge_weighted(7, 1, 1); // Unreachable from 1
+        graph
+    }
+
     #[test]
-    fn test_multiple_shortest_paths() {
-        let mut graph: Graph<i32, usize> = Graph::directed();
+    fn test_run_to_completion() {
+        let graph = complex_graph();
+        let tree = Dijkstra::new(&graph, 1).run_to_completion();
+
+        assert_eq!(tree.len(), 6);
+        assert!(!tree.contains(&7));
+        assert_eq!(tree.distance_to(&1), Some(0));
+        assert_eq!(tree.distance_to(&6), Some(8));
+        assert_eq!(tree.distance_to(&7), None);
+        assert_eq!(tree.path_to(&1), Some(vec![1]));
+        assert_eq!(tree.path_to(&6), Some(vec![1, 2, 5, 6]));
+        assert_eq!(tree.path_to(&7), None);
+
+        // Every distance agrees with a single-target search
+        for (node, distance) in tree.iter() {
+            let (path, expected) = graph.shortest_path(1, *node).unwrap();
+            assert_eq!(distance, expected);
+            assert_eq!(tree.path_to(node).unwrap().len(), path.len());
+        }
+    }
 
-        // Create a graph with multiple equal-length paths from 1 to 4
-        //     2
-        //   /   \
-        // 1       4
-        //   \   /
-        //     3
+    #[test]
+    fn test_run_to_completion_after_shortest_path() {
+        let graph = complex_graph();
+        let mut dijkstra = Dijkstra::new(&graph, 1);
+        assert_eq!(dijkstra.shortest_path(&2).unwrap().1, 4);
+
+        let tree = dijkstra.run_to_completion();
+        let mut distances: Vec<_> = tree.iter().map(|(&n, d)| (n, d)).collect();
+        distances.sort();
+        assert_eq!(
+            distances,
+            vec![(1, 0), (2, 4), (3, 7), (4, 8), (5, 6), (6, 8)]
+        );
+    }
+    #[test]
+    fn test_from_sources() {
+        let graph = complex_graph();
+        let tree = Dijkstra::from_sources(&graph, [1, 4]).run_to_completion();
+
+        assert_eq!(tree.distance_to(&4), Some(0));
+        assert_eq!(tree.distance_to(&5), Some(3));
+        assert_eq!(tree.path_to(&6), Some(vec![4, 5, 6]));
+        assert_eq!(tree.source_of(&3), Some(&1));
+        assert_eq!(tree.source_of(&6), Some(&4));
+        assert_eq!(tree.source_of(&7), None);
+
+        let mut dijkstra = Dijkstra::from_sources(&graph, [2, 3]);
+        assert_eq!(dijkstra.shortest_path(&6), Some((vec![2, 5, 6], 4)));
+    }
+}
+
+#[cfg(test)]
+mod all_shortest_paths_tests {
+    use super::*;
+
+    #[test]
+    fn test_multiple_shortest_paths() {
+        let mut graph: Graph<i32, usize> = Graph::directed();
+
+        // Create a graph with multiple equal-length paths from 1 to 4
+        //     2
+        //   /   \
+        // 1       4
+        //   \   /
+        //     3
         graph.add_edge_weighted(1, 2, 1);
         graph.add_edge_weighted(1, 3, 1);
         graph.add_edge_weighted(2, 4, 1);
         graph.add_edge_weighted(3, 4, 1);
 
-        let mut dijkstra = Dijkstra::new(&graph, 1);
-        let (paths, distance) = dijkstra.all_shortest_paths(&4).unwrap();
+        let dijkstra = Dijkstra::new(&graph, 1);
+        let (paths, distance) = dijkstra.all_shortest_paths(&4).unwrap().unwrap();
 
         assert_eq!(distance, 2);
         assert_eq!(paths.len(), 2);
@@ -874,8 +2272,8 @@ mod all_shortest_paths_tests {
         graph.add_edge_weighted(2, 3, 1);
         graph.add_edge_weighted(1, 3, 3); // Longer alternative path
 
-        let mut dijkstra = Dijkstra::new(&graph, 1);
-        let (paths, distance) = dijkstra.all_shortest_paths(&3).unwrap();
+        let dijkstra = Dijkstra::new(&graph, 1);
+        let (paths, distance) = dijkstra.all_shortest_paths(&3).unwrap().unwrap();
 
         assert_eq!(distance, 2);
         assert_eq!(paths.len(), 1);
@@ -888,7 +2286,1129 @@ mod all_shortest_paths_tests {
         graph.add_edge_weighted(1, 2, 1);
         graph.add_edge_weighted(3, 4, 1); // Disconnected
 
+        let dijkstra = Dijkstra::new(&graph, 1);
+        assert!(dijkstra.all_shortest_paths(&4).unwrap().is_none());
+    }
+}
+
+#[cfg(test)]
+mod astar_tests {
+    use super::*;
+
+    /// A `size` x `size` grid with unit-weight edges between orthogonal neighbours.
+    fn grid_graph(size: i32) -> Graph<(i32, i32), i32> {
+        let mut graph = Graph::undirected();
+        for x in 0..size {
+            for y in 0..size {
+                if x + 1 < size {
+                    graph.add_edge((x, y), (x + 1, y));
+                }
+                if y + 1 < size {
+                    graph.add_edge((x, y), (x, y + 1));
+                }
+            }
+        }
+        graph
+    }
+
+    fn manhattan(goal: (i32, i32)) -> impl Fn(&(i32, i32)) -> i32 {
+        move |&(x, y)| (goal.0 - x).abs() + (goal.1 - y).abs()
+    }
+
+    #[test]
+    fn test_astar_matches_dijkstra() {
+        let mut graph: Graph<i32, usize> = Graph::directed();
+        let edges = [
+            (1, 2, 4),
+            (2, 3, 3),
+            (1, 4, 8),
+            (2, 5, 2),
+            (3, 6, 5),
+            (4, 5, 3),
+            (5, 6, 2),
+        ];
+        for (from, to, weight) in edges {
+            graph.add_edge_weighted(from, to, weight);
+        }
+        // Admissible: at least one more edge unless already at the goal
+        let heuristic = |&n: &i32| if n == 6 { 0 } else { 2 };
+
+        for end in 1..=6 {
+            let expected = Dijkstra::new(&graph, 1).shortest_path(&end);
+            let actual = AStar::new(&graph, 1, heuristic).shortest_path(&end);
+            assert_eq!(
+                actual.map(|(_, d)| d),
+                expected.map(|(_, d)| d),
+                "end {end}"
+            );
+        }
+    }
+
+    #[test]
+    fn test_astar_repeated_search() {
+        let graph = grid_graph(5);
+        let (start, goal) = ((0, 0), (4, 2));
+
+        let mut dijkstra = Dijkstra::new(&graph, start);
+        let mut astar = AStar::new(&graph, start, manhattan(goal));
+        for _ in 0..2 {
+            let (_, expected) = dijkstra.shortest_path(&goal).unwrap();
+            let (path, distance) = astar.shortest_path(&goal).unwrap();
+            assert_eq!(distance, expected);
+            assert_eq!((path[0], path[path.len() - 1]), (start, goal));
+        }
+
+        // Without a heuristic, a later search can also continue on to a farther goal
+        let mut dijkstra = Dijkstra::new(&graph, start);
+        let mut blind = AStar::new(&graph, start, |_: &(i32, i32)| 0);
+        for end in [(1, 1), (1, 1), (3, 2), (4, 4)] {
+            let expected = dijkstra.shortest_path(&end).map(|(_, d)| d);
+            assert_eq!(blind.shortest_path(&end).map(|(_, d)| d), expected);
+        }
+    }
+
+    #[test]
+    fn test_astar_grid() {
+        let graph = grid_graph(10);
+        let (start, goal) = ((0, 0), (9, 6));
+
+        let mut dijkstra = Dijkstra::new(&graph, start);
+        let (_, expected) = dijkstra.shortest_path(&goal).unwrap();
+
+        let mut astar = AStar::new(&graph, start, manhattan(goal));
+        let (path, distance) = astar.shortest_path(&goal).unwrap();
+
+        assert_eq!(distance, expected);
+        assert_eq!(path.len() as i32, distance + 1);
+        assert_eq!((path[0], path[path.len() - 1]), (start, goal));
+        assert!(path.windows(2).all(|w| graph.has_edge(&w[0], &w[1])));
+
+        // A perfect heuristic should explore fewer nodes than no heuristic at all
+        let mut blind = AStar::new(&graph, start, |_: &(i32, i32)| 0);
+        assert_eq!(blind.shortest_path(&goal).unwrap().1, expected);
+        assert!(astar.expanded() < blind.expanded());
+    }
+
+    #[test]
+    fn test_astar_no_path() {
+        let mut graph: Graph<i32, usize> = Graph::directed();
+        graph.add_edge_weighted(1, 2, 1);
+        graph.add_edge_weighted(3, 4, 1);
+
+        let mut astar = AStar::new(&graph, 1, |_| 0);
+        assert_eq!(astar.shortest_path(&4), None);
+        assert_eq!(astar.expanded(), 2);
+    }
+}
+
+#[cfg(test)]
+mod shortest_path_dag_tests {
+    use super::*;
+
+    /// A `width` x `height` lattice where edges only go right or down, so every monotone path
+    /// from the top left to the bottom right is a shortest one.
+    fn lattice(width: i32, height: i32) -> Graph<(i32, i32), u64> {
+        let mut graph = Graph::directed();
+        for x in 0..width {
+            for y in 0..height {
+                if x + 1 < width {
+                    graph.add_edge((x, y), (x + 1, y));
+                }
+                if y + 1 < height {
+                    graph.add_edge((x, y), (x, y + 1));
+                }
+            }
+        }
+        graph
+    }
+
+    #[test]
+    fn test_diamond() {
+        let mut graph: Graph<i32, usize> = Graph::directed();
+        graph.add_edge_weighted(1, 2, 1);
+        graph.add_edge_weighted(1, 3, 1);
+        graph.add_edge_weighted(2, 4, 1);
+        graph.add_edge_weighted(3, 4, 1);
+        graph.add_edge_weighted(1, 5, 1);
+        graph.add_edge_weighted(5, 4, 2); // Too long
+
+        let dag = Dijkstra::new(&graph, 1).shortest_path_dag(&4).unwrap();
+
+        assert_eq!(dag.distance(), 2);
+        assert_eq!(dag.count_paths::<usize>().unwrap(), 2);
+        assert_eq!(dag.nodes_on_any_path(), HashSet::from([1, 2, 3, 4]));
+        assert_eq!(
+            dag.edges_on_any_path(),
+            HashSet::from([(1, 2), (1, 3), (2, 4), (3, 4)])
+        );
+        assert_eq!(dag.paths().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_start_is_end() {
+        let mut graph: Graph<i32, usize> = Graph::directed();
+        graph.add_edge_weighted(1, 2, 1);
+
+        let dag = Dijkstra::new(&graph, 1).shortest_path_dag(&1).unwrap();
+        assert_eq!(dag.distance(), 0);
+        assert_eq!(dag.count_paths::<u8>().unwrap(), 1);
+        assert_eq!(dag.nodes_on_any_path(), HashSet::from([1]));
+        assert!(dag.edges_on_any_path().is_empty());
+    }
+
+    #[test]
+    fn test_count_lattice_paths() {
+        // Binomial(2n, n) paths would be far too many to enumerate
+        let graph = lattice(40, 40);
+        let dag = Dijkstra::new(&graph, (0, 0))
+            .shortest_path_dag(&(39, 39))
+            .unwrap();
+
+        assert_eq!(dag.distance(), 78);
+        assert_eq!(
+            dag.count_paths::<u128>().unwrap(),
+            27_217_014_869_199_032_015_600
+        );
+        assert_eq!(dag.nodes_on_any_path().len(), 40 * 40);
+        assert_eq!(dag.edges_on_any_path().len(), 2 * 39 * 40);
+
+        let small = lattice(4, 3);
+        let dag = Dijkstra::new(&small, (0, 0))
+            .shortest_path_dag(&(3, 2))
+            .unwrap();
+        assert_eq!(dag.count_paths::<u32>().unwrap(), 10);
+        assert_eq!(dag.paths().unwrap().len(), 10);
+    }
+
+    #[test]
+    fn test_zero_weight_cycle() {
+        // The source is never given a predecessor, so 1 and 2 tying at distance 0 is fine
+        let mut graph: Graph<i32, usize> = Graph::undirected();
+        graph.add_edge_weighted(1, 2, 0);
+        graph.add_edge_weighted(2, 3, 1);
+
+        let dag = Dijkstra::new(&graph, 1).shortest_path_dag(&3).unwrap();
+        assert_eq!(dag.count_paths::<usize>().unwrap(), 1);
+        assert_eq!(dag.paths().unwrap(), vec![vec![1, 2, 3]]);
+        assert_eq!(
+            Dijkstra::new(&graph, 1).all_shortest_paths(&3).unwrap(),
+            Some((vec![vec![1, 2, 3]], 1))
+        );
+
+        // Past the source, 2 and 3 can bounce back and forth forever
+        graph.add_edge_weighted(2, 3, 0);
+        graph.add_edge_weighted(3, 4, 1);
+        let dag = Dijkstra::new(&graph, 1).shortest_path_dag(&4).unwrap();
+        assert_eq!(dag.nodes_on_any_path(), HashSet::from([1, 2, 3, 4]));
+        assert!(dag.edges_on_any_path().contains(&(3, 2)));
+        let cycle = dag.count_paths::<usize>().unwrap_err();
+        assert_eq!(cycle.0.len(), 2);
+        assert!(dag.paths().is_err());
+        assert!(Dijkstra::new(&graph, 1).all_shortest_paths(&4).is_err());
+    }
+
+    #[test]
+    fn test_after_earlier_search() {
+        let mut graph: Graph<i32, usize> = Graph::directed();
+        graph.add_edge_weighted(1, 2, 1);
+        graph.add_edge_weighted(1, 3, 1);
+        graph.add_edge_weighted(2, 4, 1);
+        graph.add_edge_weighted(3, 4, 1);
+
         let mut dijkstra = Dijkstra::new(&graph, 1);
-        assert!(dijkstra.all_shortest_paths(&4).is_none());
+        assert_eq!(
+            dijkstra.shortest_path(&4).map(|(_, distance)| distance),
+            Some(2)
+        );
+        let dag = dijkstra.shortest_path_dag(&4).unwrap();
+        assert_eq!(dag.count_paths::<usize>().unwrap(), 2);
+        assert_eq!(dag.nodes_on_any_path(), HashSet::from([1, 2, 3, 4]));
+    }
+
+    #[test]
+    fn test_multiple_sources() {
+        // Paths start from the last source they pass, so 2 is not given 1 as a predecessor
+        let mut graph: Graph<i32, usize> = Graph::directed();
+        graph.add_edge_weighted(1, 2, 0);
+        graph.add_edge_weighted(2, 3, 1);
+
+        let dag = Dijkstra::from_sources(&graph, [1, 2])
+            .shortest_path_dag(&3)
+            .unwrap();
+        assert_eq!(dag.paths().unwrap(), vec![vec![2, 3]]);
+    }
+}
+
+#[cfg(test)]
+mod shortest_path_goal_tests {
+    use super::*;
+
+    /// Deterministic xorshift generator, so failures are reproducible.
+    struct Rng(u64);
+
+    impl Rng {
+        fn below(&mut self, n: u64) -> u64 {
+            self.0 ^= self.0 << 13;
+            self.0 ^= self.0 >> 7;
+            self.0 ^= self.0 << 17;
+            self.0 % n
+        }
+    }
+
+    /// A random graph on nodes `0..n`. Unless `zero_cycles` is set, zero-weight edges only
+    /// point to higher nodes of a directed graph, so every shortest path is simple.
+    fn random_graph(rng: &mut Rng, n: u64, directed: bool, zero_cycles: bool) -> Graph<u64, u64> {
+        let mut graph = if directed {
+            Graph::directed()
+        } else {
+            Graph::undirected()
+        };
+        for node in 0..n {
+            graph.add_node(node);
+        }
+        for from in 0..n {
+            for to in 0..n {
+                if from == to || (!directed && from > to) || rng.below(3) != 0 {
+                    continue;
+                }
+                let weight = rng.below(3);
+                if weight > 0 || zero_cycles || (directed && from < to) {
+                    graph.add_edge_weighted(from, to, weight);
+                }
+            }
+        }
+        graph
+    }
+
+    /// Every simple path from `start` that ends at a goal, with its length.
+    fn simple_paths(
+        graph: &Graph<u64, u64>,
+        start: u64,
+        goals: &HashSet<u64>,
+    ) -> Vec<(Vec<u64>, u64)> {
+        fn extend(
+            graph: &Graph<u64, u64>,
+            goals: &HashSet<u64>,
+            path: &mut Vec<u64>,
+            length: u64,
+            found: &mut Vec<(Vec<u64>, u64)>,
+        ) {
+            let node = *path.last().unwrap();
+            if goals.contains(&node) {
+                found.push((path.clone(), length));
+            }
+            for (&next, &weight) in graph.neighbors_weighted(&node).into_iter().flatten() {
+                if !path.contains(&next) {
+                    path.push(next);
+                    extend(graph, goals, path, length + weight, found);
+                    path.pop();
+                }
+            }
+        }
+
+        let mut found = Vec::new();
+        extend(graph, goals, &mut vec![start], 0, &mut found);
+        found
+    }
+
+    /// All-pairs distances by Floyd-Warshall, ignoring edges back into `start`.
+    fn distances_avoiding(graph: &Graph<u64, u64>, n: u64, start: u64) -> Vec<Vec<Option<u64>>> {
+        let n = n as usize;
+        let mut distance = vec![vec![None; n]; n];
+        for (from, row) in distance.iter_mut().enumerate() {
+            row[from] = Some(0);
+            for (&to, &weight) in graph
+                .neighbors_weighted(&(from as u64))
+                .into_iter()
+                .flatten()
+            {
+                if to != start {
+                    let current = &mut row[to as usize];
+                    *current = Some(current.map_or(weight, |c: u64| c.min(weight)));
+                }
+            }
+        }
+        for via in 0..n {
+            for from in 0..n {
+                for to in 0..n {
+                    if let (Some(a), Some(b)) = (distance[from][via], distance[via][to]) {
+                        let current = &mut distance[from][to];
+                        *current = Some(current.map_or(a + b, |c| c.min(a + b)));
+                    }
+                }
+            }
+        }
+        distance
+    }
+
+    #[test]
+    fn test_zero_weight_tie_after_goal() {
+        // 0 -> 3 directly, or through 2 via a zero-weight edge; ties pop the larger node first,
+        // so 3 is popped before 2 has been expanded
+        let mut graph: Graph<i32, usize> = Graph::directed();
+        graph.add_edge_weighted(0, 3, 1);
+        graph.add_edge_weighted(0, 2, 1);
+        graph.add_edge_weighted(2, 3, 0);
+
+        let (paths, distance) = Dijkstra::new(&graph, 0)
+            .all_shortest_paths(&3)
+            .unwrap()
+            .unwrap();
+        assert_eq!(distance, 1);
+        assert_eq!(paths.len(), 2);
+        assert!(paths.contains(&vec![0, 2, 3]));
+    }
+
+    #[test]
+    fn test_goal_set() {
+        let mut graph: Graph<i32, usize> = Graph::directed();
+        graph.add_edge_weighted(0, 1, 2);
+        graph.add_edge_weighted(0, 2, 2);
+        graph.add_edge_weighted(0, 3, 5);
+
+        let goals = HashSet::from([1, 2, 3]);
+        let dag = Dijkstra::new(&graph, 0)
+            .shortest_path_dag_to_any(&goals)
+            .unwrap();
+        let mut ends = dag.ends().to_vec();
+        ends.sort();
+        assert_eq!(ends, vec![1, 2]);
+        assert_eq!(dag.distance(), 2);
+        assert_eq!(dag.count_paths::<usize>().unwrap(), 2);
+
+        let dag = Dijkstra::new(&graph, 0)
+            .shortest_path_dag_where(|&n| n > 2)
+            .unwrap();
+        assert_eq!((dag.ends(), dag.distance()), (&[3][..], 5));
+
+        assert!(Dijkstra::new(&graph, 1)
+            .shortest_path_dag_where(|&n| n == 0)
+            .is_none());
+    }
+
+    #[test]
+    fn test_where_variants() {
+        // Nodes are (position, facing); the goal is position 3 with any facing
+        let mut graph: Graph<(i32, char), usize> = Graph::directed();
+        graph.add_edge_weighted((0, 'a'), (1, 'a'), 1);
+        graph.add_edge_weighted((1, 'a'), (3, 'a'), 1);
+        graph.add_edge_weighted((0, 'a'), (2, 'b'), 1);
+        graph.add_edge_weighted((2, 'b'), (3, 'b'), 1);
+        graph.add_edge_weighted((0, 'a'), (3, 'c'), 5);
+
+        let at_goal = |&(position, _): &(i32, char)| position == 3;
+        let (path, distance) = Dijkstra::new(&graph, (0, 'a'))
+            .shortest_path_where(at_goal)
+            .unwrap();
+        assert_eq!(distance, 2);
+        assert!(at_goal(path.last().unwrap()));
+
+        let (mut paths, distance) = Dijkstra::new(&graph, (0, 'a'))
+            .all_shortest_paths_where(at_goal)
+            .unwrap()
+            .unwrap();
+        paths.sort();
+        assert_eq!(distance, 2);
+        assert_eq!(
+            paths,
+            vec![
+                vec![(0, 'a'), (1, 'a'), (3, 'a')],
+                vec![(0, 'a'), (2, 'b'), (3, 'b')]
+            ]
+        );
+
+        assert!(Dijkstra::new(&graph, (0, 'a'))
+            .shortest_path_where(|&(position, _)| position > 3)
+            .is_none());
+    }
+
+    #[test]
+    fn test_against_brute_force() {
+        let mut rng = Rng(0x2024_1216);
+        for _ in 0..600 {
+            let n = 2 + rng.below(6);
+            let directed = rng.below(2) == 0;
+            let zero_cycles = rng.below(2) == 0;
+            let graph = random_graph(&mut rng, n, directed, zero_cycles);
+            let start = rng.below(n);
+            let goals: HashSet<u64> = (0..n).filter(|_| rng.below(3) == 0).collect();
+
+            let brute = simple_paths(&graph, start, &goals);
+            let best = brute.iter().map(|&(_, length)| length).min();
+            let mut expected: Vec<Vec<u64>> = brute
+                .into_iter()
+                .filter(|&(_, length)| Some(length) == best)
+                .map(|(path, _)| path)
+                .collect();
+            expected.sort();
+
+            let dag = Dijkstra::new(&graph, start).shortest_path_dag_to_any(&goals);
+            assert_eq!(dag.as_ref().map(|dag| dag.distance()), best);
+            let Some(dag) = dag else { continue };
+
+            // Shortest walks may repeat nodes round zero-weight cycles, but never the start
+            let distance = distances_avoiding(&graph, n, start);
+            let via = |from: u64, to: u64| distance[from as usize][to as usize];
+            let to_goal = |node: u64| goals.iter().filter_map(|&goal| via(node, goal)).min();
+            let best = best.unwrap();
+            let on_walk =
+                |node: u64| via(start, node).zip(to_goal(node)).map(|(a, b)| a + b) == Some(best);
+            let nodes: HashSet<u64> = (0..n).filter(|&node| on_walk(node)).collect();
+            let edges: HashSet<(u64, u64)> = (0..n)
+                .filter_map(|from| Some((from, via(start, from)?)))
+                .flat_map(|(from, so_far)| {
+                    let neighbors = graph.neighbors_weighted(&from).into_iter().flatten();
+                    neighbors.map(move |(&to, &weight)| (from, so_far + weight, to))
+                })
+                .filter(|&(_, so_far, to)| {
+                    to != start && to_goal(to).map(|rest| so_far + rest) == Some(best)
+                })
+                .map(|(from, _, to)| (from, to))
+                .collect();
+            assert_eq!(dag.nodes_on_any_path(), nodes, "{graph:?} from {start}");
+            assert_eq!(dag.edges_on_any_path(), edges, "{graph:?} from {start}");
+
+            let infinite = edges.iter().any(|&(from, to)| {
+                graph
+                    .neighbors_weighted(&from)
+                    .into_iter()
+                    .flatten()
+                    .any(|(&next, &weight)| next == to && weight == 0 && via(to, from) == Some(0))
+            });
+            if infinite {
+                assert!(
+                    dag.count_paths::<usize>().is_err(),
+                    "{graph:?} from {start}"
+                );
+                assert!(dag.paths().is_err(), "{graph:?} from {start}");
+            } else {
+                let mut paths = dag.paths().unwrap();
+                paths.sort();
+                assert_eq!(paths, expected, "{graph:?} from {start} to {goals:?}");
+                assert_eq!(dag.count_paths::<usize>().unwrap(), paths.len());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod successors_tests {
+    use super::*;
+
+    /// An infinite graph: from `n` you can add one or double.
+    fn add_or_double(&n: &u64) -> [(u64, u32); 2] {
+        [(n + 1, 1), (n * 2, 1)]
+    }
+
+    #[test]
+    fn test_graph_successors() {
+        let mut graph: Graph<i32, u32> = Graph::directed();
+        graph.add_edge_weighted(1, 2, 5);
+        graph.add_edge_weighted(1, 3, 7);
+
+        let mut successors: Vec<_> = graph.successors(&1).collect();
+        successors.sort();
+        assert_eq!(successors, vec![(2, 5), (3, 7)]);
+        assert_eq!(graph.successors(&2).count(), 0);
+    }
+
+    #[test]
+    fn test_implicit_bfs() {
+        // 1 -> 2 -> 3 -> 6 -> 12 -> 24 -> 25 is one of the shortest ways to 25
+        let path = Bfs::new(&add_or_double, 1)
+            .shortest_path_where(|&n| n == 25)
+            .unwrap();
+        assert_eq!(path.len(), 7);
+        assert_eq!((path[0], path[6]), (1, 25));
+    }
+
+    #[test]
+    fn test_implicit_dijkstra_and_astar() {
+        let (path, distance) = Dijkstra::new(&add_or_double, 1)
+            .shortest_path(&100)
+            .unwrap();
+        assert_eq!(distance, 8);
+        assert_eq!(path.len(), 9);
+
+        // Any node short of the goal needs at least one more step
+        let heuristic = |&n: &u64| if n >= 100 { 0 } else { 1 };
+        let mut astar = AStar::new(&add_or_double, 1, heuristic);
+        assert_eq!(astar.shortest_path(&100).unwrap().1, 8);
+
+        // Closures can also close over puzzle data
+        let walls = [3, 4, 5];
+        let successors = |&n: &i32| {
+            [n - 1, n + 1]
+                .into_iter()
+                .filter(|next| !walls.contains(next))
+                .map(|next| (next, 1u32))
+        };
+        assert_eq!(
+            Dijkstra::new(&successors, 0).shortest_path_where(|&n| n.abs() == 6),
+            Some(((0..=6).map(|n| -n).collect(), 6))
+        );
+    }
+}
+
+#[cfg(test)]
+mod dfs_tests {
+    use super::*;
+    use DfsEvent::{Edge, Enter, Exit};
+
+    fn edge(from: i32, to: i32, kind: EdgeKind) -> DfsEvent<i32> {
+        Edge { from, to, kind }
+    }
+
+    #[test]
+    fn test_dfs_events() {
+        let mut graph: Graph<i32> = Graph::directed();
+        for (from, to) in [(1, 2), (2, 3), (3, 1), (1, 3), (4, 3)] {
+            graph.add_edge(from, to);
+        }
+
+        let events: Vec<_> = graph.dfs_from([1, 4]).collect();
+        assert_eq!(
+            events,
+            vec![
+                Enter(1),
+                edge(1, 2, EdgeKind::Tree),
+                Enter(2),
+                edge(2, 3, EdgeKind::Tree),
+                Enter(3),
+                edge(3, 1, EdgeKind::Back),
+                Exit(3),
+                Exit(2),
+                edge(1, 3, EdgeKind::Forward),
+                Exit(1),
+                Enter(4),
+                edge(4, 3, EdgeKind::Cross),
+                Exit(4),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_dfs_orders() {
+        let mut graph: Graph<i32> = Graph::directed();
+        for (from, to) in [(1, 2), (1, 3), (2, 4), (3, 4)] {
+            graph.add_edge(from, to);
+        }
+
+        let mut dfs = graph.dfs(1);
+        let mut pre_order = Vec::new();
+        while let Some(node) = dfs.next_node() {
+            pre_order.push(node);
+        }
+        assert_eq!(pre_order, vec![1, 2, 4, 3]);
+
+        let post_order: Vec<i32> = graph
+            .dfs(1)
+            .filter_map(|event| match event {
+                Exit(node) => Some(node),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(post_order, vec![4, 2, 3, 1]);
+        // A DAG has no back edges
+        assert!(!graph.dfs(1).any(|event| matches!(
+            event,
+            Edge {
+                kind: EdgeKind::Back,
+                ..
+            }
+        )));
+    }
+
+    #[test]
+    fn test_dfs_deep_graph() {
+        // Far deeper than a recursive search could go
+        let depth = 100_000;
+        let successors = |&n: &u32| (n < depth).then_some((n + 1, 1u32));
+        let exits = Dfs::new(&successors, 0)
+            .filter(|event| matches!(event, Exit(_)))
+            .count();
+        assert_eq!(exits, depth as usize + 1);
+    }
+}
+
+#[cfg(test)]
+mod topological_sort_tests {
+    use super::*;
+
+    #[test]
+    fn test_topological_sort() {
+        let mut graph: Graph<char> = Graph::directed();
+        for (from, to) in [('a', 'c'), ('b', 'c'), ('c', 'd'), ('b', 'e'), ('e', 'd')] {
+            graph.add_edge(from, to);
+        }
+        graph.add_node('f');
+
+        let order = graph.topological_sort().unwrap();
+        assert_eq!(order.len(), 6);
+        let position = |n: &char| order.iter().position(|o| o == n).unwrap();
+        for (from, to) in graph.edge_pairs() {
+            assert!(position(from) < position(to), "{from} -> {to}");
+        }
+
+        assert_eq!(
+            graph.topological_layers().unwrap(),
+            vec![vec!['a', 'b', 'f'], vec!['c', 'e'], vec!['d']]
+        );
+    }
+
+    #[test]
+    fn test_cycle_reported() {
+        let mut graph: Graph<i32> = Graph::directed();
+        for (from, to) in [(1, 2), (2, 3), (3, 4), (4, 2), (4, 5)] {
+            graph.add_edge(from, to);
+        }
+
+        let cycle = graph.topological_sort().unwrap_err();
+        assert_eq!(cycle, Cycle(vec![2, 3, 4]));
+        assert_eq!(cycle.to_string(), "2 -> 3 -> 4 -> 2");
+        assert_eq!(graph.topological_layers(), Err(cycle.clone()));
+        assert!(matches!(
+            AocError::from(cycle),
+            AocError::InvalidGraph(message) if message.contains("2 -> 3 -> 4 -> 2")
+        ));
+
+        let mut graph: Graph<i32> = Graph::directed();
+        graph.add_edge(7, 7);
+        assert_eq!(graph.topological_sort(), Err(Cycle(vec![7])));
+        assert_eq!(graph.topological_layers(), Err(Cycle(vec![7])));
+
+        // The cycle is only reachable from a node that is already laid out
+        let mut graph: Graph<i32> = Graph::directed();
+        for (from, to) in [(1, 5), (5, 3), (3, 4), (4, 5), (2, 1)] {
+            graph.add_edge(from, to);
+        }
+        assert_eq!(graph.topological_layers(), Err(Cycle(vec![3, 4, 5])));
+    }
+
+    #[test]
+    fn test_order_is_depth_first() {
+        // Smallest-first would give [1, 3, 2]
+        let mut graph: Graph<i32> = Graph::directed();
+        graph.add_node(1);
+        graph.add_edge(3, 2);
+        assert_eq!(graph.topological_sort(), Ok(vec![3, 2, 1]));
+        assert_eq!(graph.topological_layers(), Ok(vec![vec![1, 3], vec![2]]));
+    }
+
+    #[test]
+    fn test_empty_and_undirected() {
+        let graph: Graph<i32> = Graph::directed();
+        assert_eq!(graph.topological_sort(), Ok(vec![]));
+        assert_eq!(graph.topological_layers(), Ok(vec![]));
+
+        let mut graph: Graph<i32> = Graph::undirected();
+        graph.add_edge(1, 2);
+        assert_eq!(graph.topological_sort(), Err(Cycle(vec![1, 2])));
+        assert_eq!(graph.topological_layers(), Err(Cycle(vec![1, 2])));
+    }
+}
+
+#[cfg(test)]
+mod component_tests {
+    use super::*;
+
+    fn example() -> Graph<i32> {
+        // Two cycles, 1-2-3 and 4-5, joined by 3 -> 4, with 6 hanging off 5 and 7 on its own
+        let mut graph = Graph::directed();
+        for (from, to) in [(1, 2), (2, 3), (3, 1), (3, 4), (4, 5), (5, 4), (5, 6)] {
+            graph.add_edge(from, to);
+        }
+        graph.add_edge(8, 7);
+        graph
+    }
+
+    #[test]
+    fn test_strongly_connected_components() {
+        let components = example().strongly_connected_components();
+        assert_eq!(components.len(), 5);
+        for expected in [vec![1, 2, 3], vec![4, 5], vec![6], vec![7], vec![8]] {
+            assert!(components.contains(&expected), "{expected:?}");
+        }
+
+        // Reverse topological order: a component only points to earlier ones
+        let position = |n: i32| components.iter().position(|c| c.contains(&n)).unwrap();
+        assert!(position(6) < position(4) && position(4) < position(1));
+        assert!(position(7) < position(8));
+    }
+
+    #[test]
+    fn test_weakly_connected_components() {
+        assert_eq!(
+            example().weakly_connected_components(),
+            vec![vec![1, 2, 3, 4, 5, 6], vec![7, 8]]
+        );
+    }
+
+    #[test]
+    fn test_condensation() {
+        let mut graph = example();
+        graph.add_edge_weighted(2, 5, 9);
+        let condensation = graph.condensation();
+
+        assert_eq!(condensation.components.len(), 5);
+        let id = |n: i32| condensation.component_of[&n];
+        assert_eq!(id(1), id(3));
+        assert_eq!(condensation.components[id(4)], vec![4, 5]);
+
+        let dag = &condensation.dag;
+        assert_eq!(dag.nodes().len(), 5);
+        assert!(dag.has_edge(&id(1), &id(4)));
+        assert!(dag.has_edge(&id(4), &id(6)));
+        assert!(dag.has_edge(&id(8), &id(7)));
+        assert_eq!(dag.edges().count(), 3);
+        // Parallel edges 3 -> 4 and 2 -> 5 collapse to the lighter one
+        assert_eq!(dag.get_weight(&id(1), &id(4)), Some(&1));
+
+        // Component ids are a topological order of the DAG
+        assert!(dag.edge_pairs().all(|(from, to)| from < to));
+        assert!(dag.topological_sort().is_ok());
+    }
+}
+
+#[cfg(test)]
+mod mutation_tests {
+    use super::*;
+
+    fn assert_symmetric(graph: &Graph<i32, u32>) {
+        for (from, to, weight) in graph.edges() {
+            assert_eq!(graph.get_weight(to, from), Some(weight), "{from} - {to}");
+        }
+    }
+
+    #[test]
+    fn test_remove_edge() {
+        let mut graph: Graph<i32, u32> = Graph::undirected();
+        graph.add_edge_weighted(1, 2, 5);
+        graph.add_edge_weighted(2, 3, 6);
+
+        assert_eq!(graph.remove_edge(&2, &1), Some(5));
+        assert_eq!(graph.remove_edge(&2, &1), None);
+        assert!(!graph.has_edge(&1, &2));
+        assert_eq!(graph.nodes(), vec![&1, &2, &3]);
+        assert_symmetric(&graph);
+
+        let mut graph: Graph<i32, u32> = Graph::directed();
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 1);
+        assert_eq!(graph.remove_edge(&1, &2), Some(1));
+        assert!(graph.has_edge(&2, &1));
+    }
+
+    #[test]
+    fn test_remove_node() {
+        let mut graph: Graph<i32, u32> = Graph::undirected();
+        graph.add_edge(1, 2);
+        graph.add_edge(2, 3);
+        graph.add_edge(3, 1);
+
+        assert!(graph.remove_node(&2));
+        assert!(!graph.remove_node(&2));
+        assert_eq!(graph.nodes(), vec![&1, &3]);
+        assert_eq!(graph.edges().count(), 2);
+        assert_symmetric(&graph);
+
+        // A node that only appears as an edge target
+        let mut graph: Graph<i32, u32> = Graph::directed();
+        graph.add_edge(1, 2);
+        assert!(graph.remove_node(&2));
+        assert_eq!(graph.nodes(), vec![&1]);
+        assert_eq!(graph.out_degree(&1), 0);
+    }
+
+    #[test]
+    fn test_retain_nodes() {
+        let mut graph: Graph<i32, u32> = Graph::undirected();
+        for n in 1..6 {
+            graph.add_edge(n, n + 1);
+        }
+        graph.retain_nodes(|&n| n % 3 != 0);
+
+        assert_eq!(graph.nodes(), vec![&1, &2, &4, &5]);
+        assert_eq!(graph.edges().count(), 4);
+        assert!(graph.has_edge(&4, &5));
+        assert!(!graph.has_edge(&2, &3));
+        assert_symmetric(&graph);
+    }
+
+    #[test]
+    fn test_contract_edge_undirected() {
+        //   1 --5-- 2 --1-- 3 --7-- 4
+        //    \--2--------/
+        let mut graph: Graph<i32, u32> = Graph::undirected();
+        graph.add_edge_weighted(1, 2, 5);
+        graph.add_edge_weighted(2, 3, 1);
+        graph.add_edge_weighted(1, 3, 2);
+        graph.add_edge_weighted(3, 4, 7);
+
+        assert!(graph.contract_edge(&2, &3));
+        assert_eq!(graph.nodes(), vec![&1, &2, &4]);
+        // 1-2 (5) and 1-3 (2) are now parallel, so the lighter one is kept
+        assert_eq!(graph.get_weight(&1, &2), Some(&2));
+        assert_eq!(graph.get_weight(&2, &4), Some(&7));
+        assert!(!graph.has_edge(&2, &2));
+        assert_symmetric(&graph);
+
+        assert!(!graph.contract_edge(&1, &4));
+        assert_eq!(graph.nodes().len(), 3);
+    }
+
+    #[test]
+    fn test_contract_edge_directed() {
+        let mut graph: Graph<i32, u32> = Graph::directed();
+        graph.add_edge_weighted(0, 2, 3);
+        graph.add_edge_weighted(1, 2, 4);
+        graph.add_edge_weighted(2, 3, 5);
+        graph.add_edge_weighted(3, 1, 6);
+
+        assert!(!graph.contract_edge(&2, &1));
+        assert!(graph.contract_edge(&1, &2));
+        assert_eq!(graph.nodes(), vec![&0, &1, &3]);
+        assert_eq!(graph.get_weight(&0, &1), Some(&3));
+        assert_eq!(graph.get_weight(&1, &3), Some(&5));
+        assert_eq!(graph.get_weight(&3, &1), Some(&6));
+    }
+
+    #[test]
+    fn test_degrees() {
+        let mut graph: Graph<i32, u32> = Graph::directed();
+        graph.add_edge(1, 2);
+        graph.add_edge(1, 3);
+        graph.add_edge(3, 2);
+
+        assert_eq!((graph.in_degree(&1), graph.out_degree(&1)), (0, 2));
+        assert_eq!((graph.in_degree(&2), graph.out_degree(&2)), (2, 0));
+        assert_eq!(graph.degree(&3), 2);
+        assert_eq!(graph.degree(&9), 0);
+
+        let mut graph: Graph<i32, u32> = Graph::undirected();
+        graph.add_edge(1, 2);
+        graph.add_edge(1, 3);
+        assert_eq!(graph.degree(&1), 2);
+        assert_eq!(graph.in_degree(&1), graph.out_degree(&1));
+        assert_eq!(graph.degree(&2), 1);
+    }
+}
+
+#[cfg(test)]
+mod contract_chains_tests {
+    use super::*;
+
+    /// Open cells of a maze, joined to their orthogonal open neighbours.
+    fn maze(rows: &[&str]) -> Graph<(usize, usize)> {
+        let open: HashSet<(usize, usize)> = rows
+            .iter()
+            .enumerate()
+            .flat_map(|(y, row)| {
+                row.chars()
+                    .enumerate()
+                    .filter(|&(_, c)| c != '#')
+                    .map(move |(x, _)| (x, y))
+            })
+            .collect();
+
+        let mut graph = Graph::undirected();
+        for &(x, y) in &open {
+            graph.add_node((x, y));
+            for next in [(x + 1, y), (x, y + 1)] {
+                if open.contains(&next) {
+                    graph.add_edge((x, y), next);
+                }
+            }
+        }
+        graph
+    }
+
+    const MAZE: [&str; 7] = [
+        "#########",
+        "#S....#.#",
+        "#.###.#.#",
+        "#.#.....#",
+        "#.#.###.#",
+        "#...#..E#",
+        "#########",
+    ];
+
+    #[test]
+    fn test_contract_chains() {
+        let graph = maze(&MAZE);
+        let (start, end) = ((1, 1), (7, 5));
+        let contracted = graph
+            .contract_chains(|node| *node == start || *node == end)
+            .unwrap();
+
+        let nodes = contracted.graph.nodes();
+        assert!(nodes.len() < graph.nodes().len() / 3);
+        assert!(nodes.contains(&&start) && nodes.contains(&&end));
+        // Junctions and dead ends survive, corridor cells do not
+        assert!(nodes.contains(&&(5, 3)) && nodes.contains(&&(7, 1)) && nodes.contains(&&(5, 5)));
+        assert!(!nodes.contains(&&(3, 1)));
+
+        let (_, expected) = graph.shortest_path(start, end).unwrap();
+        let (path, distance) = contracted.graph.shortest_path(start, end).unwrap();
+        assert_eq!(distance, expected);
+
+        let route = contracted.expand_path(&path).unwrap();
+        assert_eq!(route.len() as u32, distance + 1);
+        assert_eq!((route[0], route[route.len() - 1]), (start, end));
+        assert!(route.windows(2).all(|w| graph.has_edge(&w[0], &w[1])));
+    }
+
+    #[test]
+    fn test_chain_lookup() {
+        let mut graph: Graph<i32> = Graph::undirected();
+        for n in 1..5 {
+            graph.add_edge(n, n + 1);
+        }
+        graph.add_edge(3, 6);
+
+        let contracted = graph.contract_chains(|_| false).unwrap();
+        assert_eq!(contracted.graph.nodes(), vec![&1, &3, &5, &6]);
+        assert_eq!(contracted.graph.get_weight(&3, &5), Some(&2));
+        assert_eq!(contracted.chain(&3, &5), Some(&[4][..]));
+        assert_eq!(contracted.chain(&5, &3), Some(&[4][..]));
+        assert_eq!(contracted.chain(&1, &3), Some(&[2][..]));
+        assert_eq!(contracted.chain(&3, &6), Some(&[][..]));
+        assert_eq!(contracted.chain(&1, &5), None);
+        assert_eq!(
+            contracted.expand_path(&[1, 3, 5]),
+            Some(vec![1, 2, 3, 4, 5])
+        );
+        assert_eq!(contracted.expand_path(&[1, 5]), None);
+
+        // Pinning keeps a corridor node
+        let contracted = graph.contract_chains(|&n| n == 4).unwrap();
+        assert_eq!(contracted.graph.nodes(), vec![&1, &3, &4, &5, &6]);
+    }
+
+    #[test]
+    fn test_parallel_chains_and_loops() {
+        // Two routes between 1 and 4, of length 2 and 3, plus a loop 4-5-6-4
+        let mut graph: Graph<i32> = Graph::undirected();
+        for (from, to) in [
+            (1, 2),
+            (2, 4),
+            (1, 3),
+            (3, 7),
+            (7, 4),
+            (4, 5),
+            (5, 6),
+            (6, 4),
+        ] {
+            graph.add_edge(from, to);
+        }
+
+        // Unpinned, 1 is just part of a loop through 4
+        let contracted = graph.contract_chains(|_| false).unwrap();
+        assert_eq!(contracted.graph.nodes(), vec![&4]);
+        assert_eq!(contracted.graph.edges().count(), 0);
+
+        let contracted = graph.contract_chains(|&n| n == 1).unwrap();
+        assert_eq!(contracted.graph.nodes(), vec![&1, &4]);
+        assert_eq!(contracted.graph.get_weight(&1, &4), Some(&2));
+        assert_eq!(contracted.chain(&4, &1), Some(&[2][..]));
+        assert!(!contracted.graph.has_edge(&4, &4));
+
+        let directed: Graph<i32> = Graph::directed();
+        assert!(directed.contract_chains(|_| false).is_err());
+    }
+}
+
+#[cfg(test)]
+mod bellman_ford_tests {
+    use super::*;
+
+    #[test]
+    fn test_negative_edges() {
+        // Dijkstra would settle 3 at distance 2 before finding the cheaper route through 2
+        let mut graph: Graph<char, i32> = Graph::directed();
+        graph.add_edge_weighted('a', 'b', 4);
+        graph.add_edge_weighted('a', 'c', 2);
+        graph.add_edge_weighted('b', 'c', -3);
+        graph.add_edge_weighted('c', 'd', 1);
+        graph.add_edge_weighted('e', 'a', 1);
+
+        let tree = BellmanFord::new(&graph, 'a').run().unwrap();
+        assert_eq!(tree.distance_to(&'c'), Some(1));
+        assert_eq!(tree.distance_to(&'d'), Some(2));
+        assert_eq!(tree.path_to(&'d'), Some(vec!['a', 'b', 'c', 'd']));
+        assert_eq!(tree.distance_to(&'e'), None);
+        assert_eq!(tree.len(), 4);
+    }
+
+    #[test]
+    fn test_matches_dijkstra_without_negative_edges() {
+        let mut graph: Graph<i32, i32> = Graph::undirected();
+        let edges = [
+            (1, 2, 7),
+            (1, 3, 9),
+            (1, 6, 14),
+            (2, 3, 10),
+            (2, 4, 15),
+            (3, 4, 11),
+            (3, 6, 2),
+            (4, 5, 6),
+            (5, 6, 9),
+        ];
+        for (from, to, weight) in edges {
+            graph.add_edge_weighted(from, to, weight);
+        }
+
+        let expected = Dijkstra::new(&graph, 1).run_to_completion();
+        let tree = BellmanFord::new(&graph, 1).run().unwrap();
+        for node in 1..=6 {
+            assert_eq!(tree.distance_to(&node), expected.distance_to(&node));
+        }
+        assert_eq!(tree.distance_to(&5), Some(20));
+    }
+
+    #[test]
+    fn test_negative_cycle() {
+        let mut graph: Graph<i32, i32> = Graph::directed();
+        graph.add_edge_weighted(0, 1, 1);
+        graph.add_edge_weighted(1, 2, 2);
+        graph.add_edge_weighted(2, 3, -4);
+        graph.add_edge_weighted(3, 1, 1);
+        graph.add_edge_weighted(3, 4, 1);
+
+        let cycle = BellmanFord::new(&graph, 0).run().unwrap_err();
+        assert_eq!(cycle, Cycle(vec![1, 2, 3]));
+        assert!(matches!(AocError::from(cycle), AocError::InvalidGraph(_)));
+
+        // Unreachable from the source, so it does not matter
+        assert!(BellmanFord::new(&graph, 4).run().is_ok());
+
+        // An undirected negative edge can be walked back and forth forever
+        let mut graph: Graph<i32, i32> = Graph::undirected();
+        graph.add_edge_weighted(1, 2, 3);
+        graph.add_edge_weighted(2, 3, -1);
+        assert_eq!(
+            BellmanFord::new(&graph, 1).run().unwrap_err(),
+            Cycle(vec![2, 3])
+        );
+    }
+
+    #[test]
+    fn test_multiple_sources() {
+        let mut graph: Graph<i32, i32> = Graph::directed();
+        graph.add_edge_weighted(1, 3, 5);
+        graph.add_edge_weighted(2, 3, -2);
+
+        let tree = BellmanFord::from_sources(&graph, [1, 2]).run().unwrap();
+        assert_eq!(tree.distance_to(&3), Some(-2));
+        assert_eq!(tree.source_of(&3), Some(&2));
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Negative edge weight")]
+    fn test_dijkstra_rejects_negative_weights() {
+        let mut graph: Graph<i32, i32> = Graph::directed();
+        graph.add_edge_weighted(1, 2, -1);
+        Dijkstra::new(&graph, 1).shortest_path(&2);
     }
 }
@@ -0,0 +1,980 @@
+use crate::{AocError, Direction, Point};
+use itertools::Itertools;
+use std::collections::HashMap;
+use std::ops::{Index, IndexMut};
+
+mod regions;
+pub mod search;
+
+pub use regions::{RegionStats, Regions};
+
+/// An axis-aligned rectangle of grid cells, `x..x + width` by `y..y + height`.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq)]
+pub struct Rect {
+    pub x: usize,
+    pub y: usize,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl Rect {
+    pub fn new(x: usize, y: usize, width: usize, height: usize) -> Self {
+        Rect {
+            x,
+            y,
+            width,
+            height,
+        }
+    }
+
+    pub fn contains(&self, x: usize, y: usize) -> bool {
+        (self.x..self.x + self.width).contains(&x) && (self.y..self.y + self.height).contains(&y)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+pub struct Grid<T> {
+    pub data: Vec<T>,
+    pub width: usize,
+    pub height: usize,
+}
+
+impl<T> Grid<T> {
+    pub fn new(width: usize, height: usize, default: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid {
+            data: vec![default; width * height],
+            width,
+            height,
+        }
+    }
+
+    /// Parses a rectangular block of text, converting each character with `convert`.
+    ///
+    /// Every line must have the same width. Errors report the 1-based line and column of the
+    /// offending character; a line that is too long is reported at its first extra character,
+    /// and one that is too short at the column just past its end.
+    pub fn parse_str<F>(input: &str, convert: F, default: T) -> Result<Self, AocError>
+    where
+        F: Fn(char) -> Result<T, String>,
+        T: Clone,
+    {
+        Self::parse_lines(input.lines().collect(), convert, default)
+    }
+
+    /// Like [`Grid::parse_str`], but ignores trailing whitespace on each line and blank lines at
+    /// the end of the input. CRLF line endings are already handled by `parse_str`; the
+    /// whitespace trimmed here includes a lone `\r` at the end of input with no final newline.
+    pub fn parse_str_lenient<F>(input: &str, convert: F, default: T) -> Result<Self, AocError>
+    where
+        F: Fn(char) -> Result<T, String>,
+        T: Clone,
+    {
+        let mut lines: Vec<&str> = input.lines().map(str::trim_end).collect();
+        while lines.last().is_some_and(|line| line.is_empty()) {
+            lines.pop();
+        }
+        Self::parse_lines(lines, convert, default)
+    }
+
+    fn parse_lines<F>(lines: Vec<&str>, convert: F, default: T) -> Result<Self, AocError>
+    where
+        F: Fn(char) -> Result<T, String>,
+        T: Clone,
+    {
+        if lines.is_empty() {
+            return Ok(Grid::new(0, 0, default));
+        }
+
+        let width = lines[0].chars().count();
+        let mut data = Vec::with_capacity(width * lines.len());
+        for (y, line) in lines.iter().enumerate() {
+            let actual = line.chars().count();
+            if let Some(extra) = line.chars().nth(width) {
+                return Err(AocError::parse(
+                    y + 1,
+                    width + 1,
+                    format!("Expected line of width {width}, found extra {extra:?}"),
+                ));
+            }
+            if actual < width {
+                return Err(AocError::parse(
+                    y + 1,
+                    actual + 1,
+                    format!("Expected line of width {width}, found end of line"),
+                ));
+            }
+
+            for (x, c) in line.chars().enumerate() {
+                let value = convert(c)
+                    .map_err(|e| AocError::parse(y + 1, x + 1, format!("Invalid {c:?}: {e}")))?;
+                data.push(value);
+            }
+        }
+
+        Ok(Grid {
+            data,
+            width,
+            height: lines.len(),
+        })
+    }
+
+    pub fn get(&self, x: usize, y: usize) -> Option<&T> {
+        if x < self.width && y < self.height {
+            Some(&self.data[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_mut(&mut self, x: usize, y: usize) -> Option<&mut T> {
+        if x < self.width && y < self.height {
+            Some(&mut self.data[y * self.width + x])
+        } else {
+            None
+        }
+    }
+
+    pub fn get_idx(&self, idx: usize) -> Option<&T> {
+        self.data.get(idx)
+    }
+
+    pub fn get_idx_mut(&mut self, idx: usize) -> Option<&mut T> {
+        self.data.get_mut(idx)
+    }
+
+    pub fn iter_row(&self, y: usize) -> impl Iterator<Item = &T> {
+        self.data[y * self.width..(y + 1) * self.width].iter()
+    }
+
+    pub fn iter_col(&self, x: usize) -> impl Iterator<Item = &T> {
+        self.data.iter().skip(x).step_by(self.width)
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        self.data.iter()
+    }
+
+    pub fn iter_items(&self) -> impl Iterator<Item = ((usize, usize), &T)> {
+        (0..self.height * self.width).map(|i| ((i % self.width, i / self.width), &self.data[i]))
+    }
+
+    /// Converts `point` to an `(x, y)` index, or `None` if it lies outside the grid.
+    pub fn checked_xy<P>(&self, point: Point<P>) -> Option<(usize, usize)>
+    where
+        (usize, usize): TryFrom<Point<P>>,
+    {
+        let (x, y) = point.try_into().ok()?;
+        (x < self.width && y < self.height).then_some((x, y))
+    }
+
+    pub fn get_point<P>(&self, point: Point<P>) -> Option<&T>
+    where
+        (usize, usize): TryFrom<Point<P>>,
+    {
+        let (x, y) = self.checked_xy(point)?;
+        self.get(x, y)
+    }
+
+    pub fn get_point_mut<P>(&mut self, point: Point<P>) -> Option<&mut T>
+    where
+        (usize, usize): TryFrom<Point<P>>,
+    {
+        let (x, y) = self.checked_xy(point)?;
+        self.get_mut(x, y)
+    }
+
+    /// The neighbouring cell in `direction`, or `None` at the edge of the grid.
+    pub fn step(&self, xy: (usize, usize), direction: Direction) -> Option<(usize, usize)> {
+        let next = Point::from(xy).checked_add_signed(direction.offset())?;
+        self.checked_xy(next)
+    }
+
+    pub fn is_within_extents(&self, x: i32, y: i32) -> bool {
+        x >= 0 && x < (self.width as i32) && y >= 0 && y < (self.height as i32)
+    }
+
+    pub fn neighbors(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
+        let mut result = Vec::new();
+        for dy in -1..=1i32 {
+            for dx in -1..=1i32 {
+                if dx == 0 && dy == 0 {
+                    continue;
+                }
+                let new_x = x as i32 + dx;
+                let new_y = y as i32 + dy;
+                if new_x >= 0
+                    && new_x < self.width as i32
+                    && new_y >= 0
+                    && new_y < self.height as i32
+                {
+                    result.push((new_x as usize, new_y as usize));
+                }
+            }
+        }
+        result
+    }
+
+    pub fn cardinal_neighbors(&self, x: usize, y: usize) -> Vec<(usize, usize)> {
+        let mut result = Vec::new();
+        for (dx, dy) in [(0, -1), (1, 0), (0, 1), (-1, 0)] {
+            let new_x = x as i32 + dx;
+            let new_y = y as i32 + dy;
+            if new_x >= 0 && new_x < self.width as i32 && new_y >= 0 && new_y < self.height as i32 {
+                result.push((new_x as usize, new_y as usize));
+            }
+        }
+        result
+    }
+
+    pub fn swap(&mut self, a: (usize, usize), b: (usize, usize)) {
+        let (ax, ay) = a;
+        let (bx, by) = b;
+        self.data.swap(ay * self.width + ax, by * self.width + bx);
+    }
+
+    pub fn idx_to_xy(&self, idx: usize) -> (usize, usize) {
+        let x = idx % self.width;
+        let y = (idx - x) / self.width;
+        (x, y)
+    }
+}
+
+/// Structural transforms. Each returns a new grid and leaves `self` untouched.
+impl<T: Clone> Grid<T> {
+    /// Builds a `width` x `height` grid whose cell `(x, y)` is `self[source(x, y)]`.
+    fn remap<F>(&self, width: usize, height: usize, source: F) -> Grid<T>
+    where
+        F: Fn(usize, usize) -> (usize, usize),
+    {
+        let data = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| self[source(x, y)].clone())
+            .collect();
+        Grid {
+            data,
+            width,
+            height,
+        }
+    }
+
+    pub fn rotate_cw(&self) -> Grid<T> {
+        self.remap(self.height, self.width, |x, y| (y, self.height - 1 - x))
+    }
+
+    pub fn rotate_ccw(&self) -> Grid<T> {
+        self.remap(self.height, self.width, |x, y| (self.width - 1 - y, x))
+    }
+
+    /// Mirrors left to right.
+    pub fn flip_horizontal(&self) -> Grid<T> {
+        self.remap(self.width, self.height, |x, y| (self.width - 1 - x, y))
+    }
+
+    /// Mirrors top to bottom.
+    pub fn flip_vertical(&self) -> Grid<T> {
+        self.remap(self.width, self.height, |x, y| (x, self.height - 1 - y))
+    }
+
+    pub fn transpose(&self) -> Grid<T> {
+        self.remap(self.height, self.width, |x, y| (y, x))
+    }
+
+    /// The cells inside `rect`. Any part of `rect` outside the grid is dropped.
+    pub fn crop(&self, rect: Rect) -> Grid<T> {
+        let x = rect.x.min(self.width);
+        let y = rect.y.min(self.height);
+        let width = rect.width.min(self.width - x);
+        let height = rect.height.min(self.height - y);
+        self.remap(width, height, |dx, dy| (x + dx, y + dy))
+    }
+
+    /// Surrounds the grid with a border `n` cells thick.
+    pub fn pad(&self, n: usize, fill: T) -> Grid<T> {
+        let mut grid = Grid::new(self.width + 2 * n, self.height + 2 * n, fill);
+        for ((x, y), value) in self.iter_items() {
+            grid[(x + n, y + n)] = value.clone();
+        }
+        grid
+    }
+
+    /// Repeats the grid `nx` times across and `ny` times down.
+    pub fn tile(&self, nx: usize, ny: usize) -> Grid<T> {
+        self.remap(self.width * nx, self.height * ny, |x, y| {
+            (x % self.width, y % self.height)
+        })
+    }
+}
+
+impl<T> Grid<T> {
+    /// Replaces every cell with the `k` cells `expand` returns for it, side by side.
+    ///
+    /// Panics if `expand` does not return exactly `k` cells.
+    pub fn scale_x<U, I, F>(&self, k: usize, expand: F) -> Grid<U>
+    where
+        F: Fn(&T) -> I,
+        I: IntoIterator<Item = U>,
+    {
+        let mut data = Vec::with_capacity(self.data.len() * k);
+        for value in &self.data {
+            let before = data.len();
+            data.extend(expand(value));
+            assert_eq!(
+                data.len() - before,
+                k,
+                "scale_x: expected {k} cells per cell"
+            );
+        }
+        Grid {
+            data,
+            width: self.width * k,
+            height: self.height,
+        }
+    }
+}
+
+// Implement Index/IndexMut for convenient access with []
+impl<T> Index<(usize, usize)> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (usize, usize)) -> &Self::Output {
+        &self.data[y * self.width + x]
+    }
+}
+
+impl<T> IndexMut<(usize, usize)> for Grid<T> {
+    fn index_mut(&mut self, (x, y): (usize, usize)) -> &mut Self::Output {
+        &mut self.data[y * self.width + x]
+    }
+}
+
+impl<T> Index<Point<usize>> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, point: Point<usize>) -> &Self::Output {
+        &self[(point.x, point.y)]
+    }
+}
+
+impl<T> IndexMut<Point<usize>> for Grid<T> {
+    fn index_mut(&mut self, point: Point<usize>) -> &mut Self::Output {
+        &mut self[(point.x, point.y)]
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for y in 0..self.height {
+            if y > 0 {
+                writeln!(f)?;
+            }
+            for x in 0..self.width {
+                write!(f, "{}", self[(x, y)])?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<T: std::fmt::Debug> std::fmt::Debug for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "Grid {{")?;
+        writeln!(f, "  width: {}", self.width)?;
+        writeln!(f, "  height: {}", self.height)?;
+        writeln!(f, "  data:")?;
+        for y in 0..self.height {
+            write!(f, "    ")?;
+            for x in 0..self.width {
+                write!(f, "{:?} ", self[(x, y)])?;
+            }
+            writeln!(f)?;
+        }
+        write!(f, "}}")
+    }
+}
+
+/// A [`Grid`] on a torus: coordinates past any edge wrap around to the opposite side.
+///
+/// Coordinates are signed and unbounded, so the grid can also be treated as an infinite plane
+/// tiled with copies of itself. The grid must have at least one cell for anything to wrap onto,
+/// so constructing one with a zero width or height panics.
+#[derive(Clone, Debug)]
+pub struct WrappingGrid<T> {
+    grid: Grid<T>,
+}
+
+impl<T> WrappingGrid<T> {
+    pub fn new(width: usize, height: usize, default: T) -> Self
+    where
+        T: Clone,
+    {
+        Grid::new(width, height, default).into()
+    }
+
+    pub fn width(&self) -> usize {
+        self.grid.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.grid.height
+    }
+
+    pub fn as_grid(&self) -> &Grid<T> {
+        &self.grid
+    }
+
+    pub fn into_inner(self) -> Grid<T> {
+        self.grid
+    }
+
+    /// Maps any coordinate onto the underlying grid.
+    pub fn wrap(&self, x: i64, y: i64) -> (usize, usize) {
+        (
+            x.rem_euclid(self.grid.width as i64) as usize,
+            y.rem_euclid(self.grid.height as i64) as usize,
+        )
+    }
+
+    /// The 8 surrounding cells, wrapped. Grids narrower than 3 cells yield repeats.
+    pub fn neighbors(&self, x: i64, y: i64) -> Vec<(usize, usize)> {
+        let mut result = Vec::with_capacity(8);
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                if dx != 0 || dy != 0 {
+                    result.push(self.wrap(x + dx, y + dy));
+                }
+            }
+        }
+        result
+    }
+
+    /// The cells above, right, below and left, wrapped.
+    pub fn cardinal_neighbors(&self, x: i64, y: i64) -> Vec<(usize, usize)> {
+        [(0, -1), (1, 0), (0, 1), (-1, 0)]
+            .into_iter()
+            .map(|(dx, dy)| self.wrap(x + dx, y + dy))
+            .collect()
+    }
+
+    pub fn iter_row(&self, y: i64) -> impl Iterator<Item = &T> {
+        self.grid.iter_row(self.wrap(0, y).1)
+    }
+
+    pub fn iter_col(&self, x: i64) -> impl Iterator<Item = &T> {
+        self.grid.iter_col(self.wrap(x, 0).0)
+    }
+
+    /// Every cell in the `width` x `height` window whose top-left corner is `(x, y)`, row by
+    /// row, paired with its unwrapped coordinate. The window may be larger than the grid.
+    pub fn iter_window(
+        &self,
+        x: i64,
+        y: i64,
+        width: usize,
+        height: usize,
+    ) -> impl Iterator<Item = ((i64, i64), &T)> {
+        (y..y + height as i64)
+            .flat_map(move |y| (x..x + width as i64).map(move |x| (x, y)))
+            .map(|(x, y)| ((x, y), &self[(x, y)]))
+    }
+}
+
+impl<T> From<Grid<T>> for WrappingGrid<T> {
+    fn from(grid: Grid<T>) -> Self {
+        assert!(
+            grid.width > 0 && grid.height > 0,
+            "Cannot wrap around an empty {}x{} grid",
+            grid.width,
+            grid.height
+        );
+        WrappingGrid { grid }
+    }
+}
+
+impl<T> Index<(i64, i64)> for WrappingGrid<T> {
+    type Output = T;
+
+    fn index(&self, (x, y): (i64, i64)) -> &Self::Output {
+        &self.grid[self.wrap(x, y)]
+    }
+}
+
+impl<T> IndexMut<(i64, i64)> for WrappingGrid<T> {
+    fn index_mut(&mut self, (x, y): (i64, i64)) -> &mut Self::Output {
+        let xy = self.wrap(x, y);
+        &mut self.grid[xy]
+    }
+}
+
+impl<T: std::fmt::Display> std::fmt::Display for WrappingGrid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        self.grid.fmt(f)
+    }
+}
+
+/// An unbounded grid keyed by signed coordinates, storing only the cells that are set.
+///
+/// The bounding box of the set cells is tracked as cells are inserted and removed.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SparseGrid<T> {
+    cells: HashMap<(i64, i64), T>,
+    min: (i64, i64),
+    max: (i64, i64),
+}
+
+impl<T> Default for SparseGrid<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> SparseGrid<T> {
+    pub fn new() -> Self {
+        SparseGrid {
+            cells: HashMap::new(),
+            min: (0, 0),
+            max: (0, 0),
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.cells.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.cells.is_empty()
+    }
+
+    /// The inclusive `(min, max)` corners of the set cells, or `None` if there are none.
+    pub fn bounds(&self) -> Option<((i64, i64), (i64, i64))> {
+        (!self.is_empty()).then_some((self.min, self.max))
+    }
+
+    pub fn width(&self) -> usize {
+        self.bounds()
+            .map_or(0, |(min, max)| (max.0 - min.0 + 1) as usize)
+    }
+
+    pub fn height(&self) -> usize {
+        self.bounds()
+            .map_or(0, |(min, max)| (max.1 - min.1 + 1) as usize)
+    }
+
+    pub fn insert(&mut self, (x, y): (i64, i64), value: T) -> Option<T> {
+        if self.is_empty() {
+            self.min = (x, y);
+            self.max = (x, y);
+        } else {
+            self.min = (self.min.0.min(x), self.min.1.min(y));
+            self.max = (self.max.0.max(x), self.max.1.max(y));
+        }
+        self.cells.insert((x, y), value)
+    }
+
+    pub fn remove(&mut self, (x, y): (i64, i64)) -> Option<T> {
+        let value = self.cells.remove(&(x, y))?;
+        if x == self.min.0 || x == self.max.0 || y == self.min.1 || y == self.max.1 {
+            self.recompute_bounds();
+        }
+        Some(value)
+    }
+
+    fn recompute_bounds(&mut self) {
+        let mut keys = self.cells.keys();
+        if let Some(&first) = keys.next() {
+            let (min, max) = keys.fold((first, first), |(min, max), &(x, y)| {
+                ((min.0.min(x), min.1.min(y)), (max.0.max(x), max.1.max(y)))
+            });
+            self.min = min;
+            self.max = max;
+        }
+    }
+
+    pub fn get(&self, x: i64, y: i64) -> Option<&T> {
+        self.cells.get(&(x, y))
+    }
+
+    pub fn get_mut(&mut self, x: i64, y: i64) -> Option<&mut T> {
+        self.cells.get_mut(&(x, y))
+    }
+
+    pub fn contains(&self, x: i64, y: i64) -> bool {
+        self.cells.contains_key(&(x, y))
+    }
+
+    /// The set cells in row-major order.
+    pub fn iter_items(&self) -> impl Iterator<Item = ((i64, i64), &T)> {
+        self.cells
+            .iter()
+            .map(|(&xy, value)| (xy, value))
+            .sorted_by_key(|&((x, y), _)| (y, x))
+    }
+
+    /// The set cells among the 8 surrounding positions.
+    pub fn neighbors(&self, x: i64, y: i64) -> Vec<(i64, i64)> {
+        let mut result = Vec::new();
+        for dy in -1..=1 {
+            for dx in -1..=1 {
+                if (dx != 0 || dy != 0) && self.contains(x + dx, y + dy) {
+                    result.push((x + dx, y + dy));
+                }
+            }
+        }
+        result
+    }
+
+    /// The set cells among the positions above, right, below and left.
+    pub fn cardinal_neighbors(&self, x: i64, y: i64) -> Vec<(i64, i64)> {
+        [(0, -1), (1, 0), (0, 1), (-1, 0)]
+            .into_iter()
+            .map(|(dx, dy)| (x + dx, y + dy))
+            .filter(|&(x, y)| self.contains(x, y))
+            .collect()
+    }
+
+    /// Crops to the bounding box, filling unset cells with `default`.
+    ///
+    /// Cell `(0, 0)` of the result is the `min` corner of [`SparseGrid::bounds`].
+    pub fn to_grid(&self, default: T) -> Grid<T>
+    where
+        T: Clone,
+    {
+        let mut grid = Grid::new(self.width(), self.height(), default);
+        for (&(x, y), value) in &self.cells {
+            let xy = ((x - self.min.0) as usize, (y - self.min.1) as usize);
+            grid[xy] = value.clone();
+        }
+        grid
+    }
+}
+
+impl<T> From<Grid<T>> for SparseGrid<T> {
+    fn from(grid: Grid<T>) -> Self {
+        let width = grid.width;
+        grid.data
+            .into_iter()
+            .enumerate()
+            .map(|(idx, value)| (((idx % width) as i64, (idx / width) as i64), value))
+            .collect()
+    }
+}
+
+impl<T> FromIterator<((i64, i64), T)> for SparseGrid<T> {
+    fn from_iter<I: IntoIterator<Item = ((i64, i64), T)>>(iter: I) -> Self {
+        let mut grid = SparseGrid::new();
+        grid.extend(iter);
+        grid
+    }
+}
+
+impl<T> Extend<((i64, i64), T)> for SparseGrid<T> {
+    fn extend<I: IntoIterator<Item = ((i64, i64), T)>>(&mut self, iter: I) {
+        for (xy, value) in iter {
+            self.insert(xy, value);
+        }
+    }
+}
+
+/// Renders the bounding box, drawing unset cells as `.`.
+impl<T: std::fmt::Display> std::fmt::Display for SparseGrid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Some((min, max)) = self.bounds() else {
+            return Ok(());
+        };
+        for y in min.1..=max.1 {
+            if y > min.1 {
+                writeln!(f)?;
+            }
+            for x in min.0..=max.0 {
+                match self.get(x, y) {
+                    Some(value) => write!(f, "{value}")?,
+                    None => write!(f, ".")?,
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use itertools::iproduct;
+
+    fn to_wall(c: char) -> Result<bool, String> {
+        match c {
+            '#' => Ok(true),
+            '.' => Ok(false),
+            _ => Err("expected '#' or '.'".to_string()),
+        }
+    }
+
+    #[test]
+    fn test_parse_str() {
+        let grid = Grid::parse_str("#.\n.#", to_wall, false).unwrap();
+        assert_eq!((grid.width, grid.height), (2, 2));
+        assert_eq!(grid.data, vec![true, false, false, true]);
+    }
+
+    #[test]
+    fn test_parse_str_reports_invalid_char() {
+        assert_eq!(
+            Grid::parse_str("#..\n.x.", to_wall, false).unwrap_err(),
+            AocError::parse(2, 2, "Invalid 'x': expected '#' or '.'")
+        );
+    }
+
+    #[test]
+    fn test_parse_str_reports_widths() {
+        assert_eq!(
+            Grid::parse_str("###\n#.#\n#.", to_wall, false).unwrap_err(),
+            AocError::parse(3, 3, "Expected line of width 3, found end of line")
+        );
+        assert_eq!(
+            Grid::parse_str("##\n##.", to_wall, false).unwrap_err(),
+            AocError::parse(2, 3, "Expected line of width 2, found extra '.'")
+        );
+    }
+
+    #[test]
+    fn test_parse_str_lenient() {
+        // CRLF needs no leniency, but trailing spaces, blank lines and a lone `\r` do
+        assert!(Grid::parse_str("#.\r\n.#\r\n", to_wall, false).is_ok());
+        for input in ["#. \n.#\n\n", "#.\n.#\r", "#.\t\r\n.#\r\n\r\n"] {
+            assert!(Grid::parse_str(input, to_wall, false).is_err(), "{input:?}");
+            let grid = Grid::parse_str_lenient(input, to_wall, false).unwrap();
+            assert_eq!((grid.width, grid.height), (2, 2), "{input:?}");
+            assert_eq!(grid.data, vec![true, false, false, true]);
+        }
+    }
+
+    #[test]
+    fn test_parse_str_lenient_reports_widths() {
+        assert_eq!(
+            Grid::parse_str_lenient("#.\n.#.# \n", to_wall, false).unwrap_err(),
+            AocError::parse(2, 3, "Expected line of width 2, found extra '.'")
+        );
+        assert_eq!(
+            Grid::parse_str_lenient("#.#\n.  \n", to_wall, false).unwrap_err(),
+            AocError::parse(2, 2, "Expected line of width 3, found end of line")
+        );
+    }
+
+    #[test]
+    fn test_get_idx() {
+        let mut grid = Grid::new(3, 2, 0);
+        *grid.get_idx_mut(4).unwrap() = 7;
+        assert_eq!(grid[(1, 1)], 7);
+        assert_eq!(grid.get_idx(4), Some(&7));
+        assert!(grid.get_idx_mut(6).is_none());
+    }
+
+    fn numbered(width: usize, height: usize) -> WrappingGrid<usize> {
+        Grid {
+            data: (0..width * height).collect(),
+            width,
+            height,
+        }
+        .into()
+    }
+
+    #[test]
+    fn test_wrapping_index() {
+        let mut grid = numbered(3, 2);
+        assert_eq!(grid[(0, 0)], 0);
+        assert_eq!(grid[(3, 0)], 0);
+        assert_eq!(grid[(-1, 0)], 2);
+        assert_eq!(grid[(-1, -1)], 5);
+        assert_eq!(grid[(7, 5)], 4);
+
+        grid[(-3, 2)] = 10;
+        assert_eq!(grid.as_grid()[(0, 0)], 10);
+    }
+
+    #[test]
+    #[should_panic(expected = "Cannot wrap around an empty 0x3 grid")]
+    fn test_wrapping_rejects_empty_grid() {
+        WrappingGrid::new(0, 3, ());
+    }
+
+    #[test]
+    fn test_wrapping_neighbors() {
+        let grid = numbered(4, 4);
+        assert_eq!(
+            grid.cardinal_neighbors(0, 0),
+            vec![(0, 3), (1, 0), (0, 1), (3, 0)]
+        );
+
+        let mut neighbors = grid.neighbors(3, 3);
+        neighbors.sort();
+        assert_eq!(
+            neighbors,
+            vec![
+                (0, 0),
+                (0, 2),
+                (0, 3),
+                (2, 0),
+                (2, 2),
+                (2, 3),
+                (3, 0),
+                (3, 2)
+            ]
+        );
+    }
+
+    #[test]
+    fn test_wrapping_iteration() {
+        let grid = numbered(3, 2);
+        assert_eq!(
+            grid.iter_row(-1).copied().collect::<Vec<_>>(),
+            vec![3, 4, 5]
+        );
+        assert_eq!(grid.iter_col(4).copied().collect::<Vec<_>>(), vec![1, 4]);
+
+        let window: Vec<_> = grid.iter_window(2, 1, 2, 2).collect();
+        assert_eq!(
+            window,
+            vec![((2, 1), &5), ((3, 1), &3), ((2, 2), &2), ((3, 2), &0)]
+        );
+    }
+
+    #[test]
+    fn test_sparse_bounds() {
+        let mut grid = SparseGrid::new();
+        assert_eq!(grid.bounds(), None);
+
+        grid.insert((-3, 2), 'a');
+        grid.insert((5, -1), 'b');
+        grid.insert((0, 0), 'c');
+        assert_eq!(grid.bounds(), Some(((-3, -1), (5, 2))));
+        assert_eq!((grid.width(), grid.height()), (9, 4));
+
+        assert_eq!(grid.remove((5, -1)), Some('b'));
+        assert_eq!(grid.bounds(), Some(((-3, 0), (0, 2))));
+        assert_eq!(grid.remove((5, -1)), None);
+
+        grid.remove((-3, 2));
+        grid.remove((0, 0));
+        assert_eq!(grid.bounds(), None);
+    }
+
+    #[test]
+    fn test_sparse_iter_and_neighbors() {
+        let grid: SparseGrid<u8> = [((1, 1), 4), ((-1, 0), 2), ((0, -1), 1), ((0, 0), 3)]
+            .into_iter()
+            .collect();
+
+        let items: Vec<_> = grid.iter_items().collect();
+        assert_eq!(
+            items,
+            vec![((0, -1), &1), ((-1, 0), &2), ((0, 0), &3), ((1, 1), &4)]
+        );
+        assert_eq!(grid.cardinal_neighbors(0, 0), vec![(0, -1), (-1, 0)]);
+        assert_eq!(grid.neighbors(0, 0), vec![(0, -1), (-1, 0), (1, 1)]);
+        assert_eq!(grid.to_string(), ".1.\n23.\n..4");
+    }
+
+    #[test]
+    fn test_sparse_dense_round_trip() {
+        let dense = Grid::parse_str("#.\n.#", to_wall, false).unwrap();
+        let mut sparse = SparseGrid::from(dense.clone());
+        assert_eq!(sparse.to_grid(false).data, dense.data);
+
+        sparse.insert((-1, 3), true);
+        let dense = sparse.to_grid(false);
+        assert_eq!((dense.width, dense.height), (3, 4));
+        let walls: Vec<_> = dense
+            .iter_items()
+            .filter(|&(_, &wall)| wall)
+            .map(|(xy, _)| xy)
+            .collect();
+        assert_eq!(walls, vec![(1, 0), (2, 1), (0, 3)]);
+    }
+
+    #[test]
+    fn test_point_indexing() {
+        let mut grid = Grid::parse_str("#.\n.#", to_wall, false).unwrap();
+        assert!(grid[Point::new(1usize, 1)]);
+
+        grid[Point::new(0usize, 1)] = true;
+        assert_eq!(grid.get_point(Point::new(0i32, 1)), Some(&true));
+        assert_eq!(grid.get_point(Point::new(-1i32, 1)), None);
+        assert_eq!(grid.get_point(Point::new(2i64, 0)), None);
+        assert_eq!(grid.checked_xy(Point::new(1isize, 0)), Some((1, 0)));
+    }
+
+    fn letters(width: usize, height: usize) -> Grid<char> {
+        Grid {
+            data: ('a'..='z').take(width * height).collect(),
+            width,
+            height,
+        }
+    }
+
+    #[test]
+    fn test_rotate_and_flip() {
+        let grid = letters(3, 2);
+        assert_eq!(grid.to_string(), "abc\ndef");
+        assert_eq!(grid.rotate_cw().to_string(), "da\neb\nfc");
+        assert_eq!(grid.rotate_ccw().to_string(), "cf\nbe\nad");
+        assert_eq!(grid.flip_horizontal().to_string(), "cba\nfed");
+        assert_eq!(grid.flip_vertical().to_string(), "def\nabc");
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf");
+    }
+
+    #[test]
+    fn test_transform_round_trips() {
+        let grid = letters(4, 3);
+        let rotated = grid.rotate_cw().rotate_cw().rotate_cw().rotate_cw();
+        assert_eq!(rotated, grid);
+        assert_eq!(grid.rotate_cw().rotate_ccw(), grid);
+        assert_eq!(grid.flip_horizontal().flip_horizontal(), grid);
+        assert_eq!(grid.flip_vertical().flip_vertical(), grid);
+        assert_eq!(grid.transpose().transpose(), grid);
+        assert_eq!(grid.transpose().flip_horizontal(), grid.rotate_cw());
+        assert_eq!(
+            grid.flip_horizontal().flip_vertical(),
+            grid.rotate_cw().rotate_cw()
+        );
+    }
+
+    #[test]
+    fn test_crop_and_pad() {
+        let grid = letters(4, 3);
+        assert_eq!(grid.crop(Rect::new(1, 1, 2, 2)).to_string(), "fg\njk");
+        assert_eq!(grid.crop(Rect::new(3, 2, 5, 5)).to_string(), "l");
+        assert_eq!(grid.crop(Rect::new(0, 0, 4, 3)), grid);
+
+        let padded = grid.pad(2, '.');
+        assert_eq!((padded.width, padded.height), (8, 7));
+        assert_eq!(padded.iter_row(0).collect::<String>(), "........");
+        assert_eq!(padded.iter_row(2).collect::<String>(), "..abcd..");
+        assert_eq!(padded.crop(Rect::new(2, 2, 4, 3)), grid);
+    }
+
+    #[test]
+    fn test_tile() {
+        let grid = letters(2, 2);
+        let tiled = grid.tile(3, 2);
+        assert_eq!(tiled.to_string(), "ababab\ncdcdcd\nababab\ncdcdcd");
+        for (tx, ty) in iproduct!(0..3, 0..2) {
+            assert_eq!(tiled.crop(Rect::new(tx * 2, ty * 2, 2, 2)), grid);
+        }
+    }
+
+    #[test]
+    fn test_scale_x() {
+        let grid = letters(2, 2);
+        let scaled = grid.scale_x(3, |&c| [c, c.to_ascii_uppercase(), c]);
+        assert_eq!(scaled.to_string(), "aAabBb\ncCcdDd");
+
+        let unscaled = Grid {
+            data: scaled.data.iter().step_by(3).copied().collect(),
+            width: 2,
+            height: 2,
+        };
+        assert_eq!(unscaled, grid);
+    }
+}
@@ -0,0 +1,180 @@
+use super::{Grid, Rect};
+use crate::{Direction, Point};
+use std::collections::VecDeque;
+
+/// Summary of one region found by [`Grid::label_regions`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct RegionStats {
+    /// Number of cells.
+    pub area: usize,
+    /// Number of cell edges that border another region or the outside of the grid.
+    pub perimeter: usize,
+    /// Number of straight fence runs along the perimeter, i.e. the number of corners.
+    pub sides: usize,
+    pub bounds: Rect,
+}
+
+/// The result of [`Grid::label_regions`].
+#[derive(Clone, Debug)]
+pub struct Regions {
+    /// The region id of each cell, indexing into `stats`.
+    pub labels: Grid<usize>,
+    pub stats: Vec<RegionStats>,
+}
+
+impl<T> Grid<T> {
+    /// Every cell reachable from `start` through orthogonally adjacent cells matching
+    /// `predicate`, in breadth-first order. Empty if `start` itself does not match.
+    pub fn flood_fill<F>(&self, start: (usize, usize), predicate: F) -> Vec<(usize, usize)>
+    where
+        F: Fn(&T) -> bool,
+    {
+        let mut filled = Vec::new();
+        if !self.get(start.0, start.1).is_some_and(&predicate) {
+            return filled;
+        }
+
+        let mut seen = vec![false; self.data.len()];
+        let mut queue = VecDeque::from([start]);
+        seen[start.1 * self.width + start.0] = true;
+
+        while let Some(xy) = queue.pop_front() {
+            filled.push(xy);
+            for next in Direction::cardinal().filter_map(|d| self.step(xy, d)) {
+                let idx = next.1 * self.width + next.0;
+                if !seen[idx] && predicate(&self.data[idx]) {
+                    seen[idx] = true;
+                    queue.push_back(next);
+                }
+            }
+        }
+        filled
+    }
+
+    /// Splits the grid into regions of orthogonally connected cells, where neighbours belong
+    /// to the same region when `same_region` holds for them. Runs in linear time.
+    pub fn label_regions<F>(&self, same_region: F) -> Regions
+    where
+        F: Fn(&T, &T) -> bool,
+    {
+        const UNLABELLED: usize = usize::MAX;
+        let mut labels = Grid::new(self.width, self.height, UNLABELLED);
+        let mut region_count = 0;
+        let mut queue = VecDeque::new();
+
+        for start in 0..self.data.len() {
+            if labels.data[start] != UNLABELLED {
+                continue;
+            }
+            labels.data[start] = region_count;
+            queue.push_back(self.idx_to_xy(start));
+
+            while let Some(xy) = queue.pop_front() {
+                for next in Direction::cardinal().filter_map(|d| self.step(xy, d)) {
+                    if labels[next] == UNLABELLED && same_region(&self[xy], &self[next]) {
+                        labels[next] = region_count;
+                        queue.push_back(next);
+                    }
+                }
+            }
+            region_count += 1;
+        }
+
+        let stats = region_stats(&labels, region_count);
+        Regions { labels, stats }
+    }
+}
+
+fn region_stats(labels: &Grid<usize>, region_count: usize) -> Vec<RegionStats> {
+    let mut stats = vec![
+        RegionStats {
+            area: 0,
+            perimeter: 0,
+            sides: 0,
+            bounds: Rect::default(),
+        };
+        region_count
+    ];
+    let mut extents = vec![((usize::MAX, usize::MAX), (0, 0)); region_count];
+
+    for ((x, y), &label) in labels.iter_items() {
+        let same = |direction: Direction| {
+            let next = Point::new(x, y).checked_add_signed(direction.offset());
+            next.and_then(|next| labels.get_point(next)) == Some(&label)
+        };
+        let region = &mut stats[label];
+
+        region.area += 1;
+        region.perimeter += Direction::cardinal().filter(|&d| !same(d)).count();
+
+        // Each side starts and ends at a corner, so count corners instead. A cell has a convex
+        // corner where both edges are exposed, and a concave one where both orthogonal
+        // neighbours are in the region but the diagonal between them is not.
+        region.sides += Direction::cardinal()
+            .filter(|&d| {
+                let (a, b) = (same(d), same(d.turn_right()));
+                (!a && !b) || (a && b && !same(d.turn_right_45()))
+            })
+            .count();
+
+        let ((min_x, min_y), (max_x, max_y)) = &mut extents[label];
+        (*min_x, *min_y) = ((*min_x).min(x), (*min_y).min(y));
+        (*max_x, *max_y) = ((*max_x).max(x), (*max_y).max(y));
+    }
+
+    for (region, ((min_x, min_y), (max_x, max_y))) in stats.iter_mut().zip(extents) {
+        region.bounds = Rect::new(min_x, min_y, max_x - min_x + 1, max_y - min_y + 1);
+    }
+    stats
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn garden(rows: &[&str]) -> Grid<char> {
+        Grid::parse_str(&rows.join("\n"), Ok, '.').unwrap()
+    }
+
+    #[test]
+    fn test_flood_fill() {
+        let grid = garden(&["..#", "###", "#.."]);
+        let mut filled = grid.flood_fill((2, 0), |&c| c == '#');
+        filled.sort();
+        assert_eq!(filled, vec![(0, 1), (0, 2), (1, 1), (2, 0), (2, 1)]);
+
+        assert_eq!(grid.flood_fill((0, 0), |&c| c == '.'), vec![(0, 0), (1, 0)]);
+        assert!(grid.flood_fill((0, 0), |&c| c == '#').is_empty());
+        assert!(grid.flood_fill((5, 5), |_| true).is_empty());
+    }
+
+    #[test]
+    fn test_label_regions() {
+        let grid = garden(&["AAAA", "BBCD", "BBCC", "EEEC"]);
+        let regions = grid.label_regions(|a, b| a == b);
+
+        assert_eq!(regions.labels.to_string(), "0000\n1123\n1122\n4442");
+        let summary: Vec<_> = regions
+            .stats
+            .iter()
+            .map(|r| (r.area, r.perimeter, r.sides))
+            .collect();
+        assert_eq!(
+            summary,
+            vec![(4, 10, 4), (4, 8, 4), (4, 10, 8), (1, 4, 4), (3, 8, 4)]
+        );
+        assert_eq!(regions.stats[2].bounds, Rect::new(2, 1, 2, 3));
+    }
+
+    #[test]
+    fn test_label_regions_with_holes() {
+        // Each X hole adds four sides to the surrounding region
+        let grid = garden(&["OOOOO", "OXOXO", "OOOOO", "OXOXO", "OOOOO"]);
+        let regions = grid.label_regions(|a, b| a == b);
+
+        assert_eq!(regions.stats.len(), 5);
+        let outer = regions.stats[regions.labels[(0, 0)]];
+        assert_eq!((outer.area, outer.perimeter, outer.sides), (21, 36, 20));
+        assert_eq!(outer.bounds, Rect::new(0, 0, 5, 5));
+    }
+}
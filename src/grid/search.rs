@@ -0,0 +1,489 @@
+//! Shortest paths over a [`Grid`] without building a [`Graph`](crate::Graph).
+//!
+//! A search state is a cell plus a `layer` for any extra state the puzzle needs, such as a
+//! facing direction. States are stored by flat id, `layer * width * height + y * width + x`,
+//! so with a single layer a state id is the cell's index into [`Grid::data`].
+
+use super::Grid;
+use crate::graph::{non_negative, Weight};
+use crate::Direction;
+use std::cmp::Ordering;
+use std::collections::{BinaryHeap, VecDeque};
+
+#[derive(Clone, Copy, Debug, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct State {
+    pub xy: (usize, usize),
+    pub layer: usize,
+}
+
+impl State {
+    pub fn new(xy: (usize, usize), layer: usize) -> Self {
+        State { xy, layer }
+    }
+}
+
+/// Distances and predecessors found by a search, indexed by state id.
+#[derive(Clone, Debug)]
+pub struct Search<W> {
+    width: usize,
+    height: usize,
+    layers: usize,
+    pub distances: Vec<Option<W>>,
+    pub predecessors: Vec<Option<usize>>,
+    goal: Option<usize>,
+}
+
+impl<W: Copy> Search<W> {
+    fn new(width: usize, height: usize, layers: usize) -> Self {
+        let len = width * height * layers;
+        Search {
+            width,
+            height,
+            layers,
+            distances: vec![None; len],
+            predecessors: vec![None; len],
+            goal: None,
+        }
+    }
+
+    /// Whether `state` lies within the grid and layers searched.
+    pub fn contains(&self, state: State) -> bool {
+        let (x, y) = state.xy;
+        x < self.width && y < self.height && state.layer < self.layers
+    }
+
+    pub fn id(&self, state: State) -> usize {
+        let (x, y) = state.xy;
+        debug_assert!(self.contains(state), "{state:?} is outside the search");
+        (state.layer * self.height + y) * self.width + x
+    }
+
+    pub fn state(&self, id: usize) -> State {
+        let cells = self.width * self.height;
+        let cell = id % cells;
+        State::new((cell % self.width, cell / self.width), id / cells)
+    }
+
+    /// The distance to `state`, or `None` if it was not reached or lies outside the search.
+    pub fn distance(&self, state: State) -> Option<W> {
+        self.distances[self.contains(state).then(|| self.id(state))?]
+    }
+
+    /// The goal state that stopped an [`astar`] or [`astar_states`] search, if one was reached.
+    pub fn goal(&self) -> Option<State> {
+        self.goal.map(|id| self.state(id))
+    }
+
+    /// State ids along a shortest path from a start to `goal`, inclusive.
+    pub fn path_ids(&self, goal: usize) -> Option<Vec<usize>> {
+        self.distances[goal]?;
+        let mut path = vec![goal];
+        while let Some(previous) = self.predecessors[*path.last()?] {
+            path.push(previous);
+        }
+        path.reverse();
+        Some(path)
+    }
+
+    pub fn path(&self, goal: State) -> Option<Vec<State>> {
+        let ids = self.path_ids(self.contains(goal).then(|| self.id(goal))?)?;
+        Some(ids.into_iter().map(|id| self.state(id)).collect())
+    }
+
+    /// The distance to each cell, taking the nearest layer.
+    pub fn distance_grid(&self) -> Grid<Option<W>>
+    where
+        W: PartialOrd,
+    {
+        let cells = self.width * self.height;
+        let mut grid = Grid::new(self.width, self.height, None);
+        for (id, distance) in self.distances.iter().enumerate() {
+            let best = &mut grid.data[id % cells];
+            if let Some(distance) = *distance {
+                if best.is_none_or(|best| distance < best) {
+                    *best = Some(distance);
+                }
+            }
+        }
+        grid
+    }
+}
+
+struct Entry<W> {
+    priority: W,
+    distance: W,
+    id: usize,
+}
+
+impl<W: PartialOrd> Ord for Entry<W> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // Flip ordering for min-heap
+        other
+            .priority
+            .partial_cmp(&self.priority)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| other.id.cmp(&self.id))
+    }
+}
+
+impl<W: PartialOrd> PartialOrd for Entry<W> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<W: PartialOrd> PartialEq for Entry<W> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<W: PartialOrd> Eq for Entry<W> {}
+
+/// Breadth-first search from `start`, moving orthogonally onto cells that are `passable`. A
+/// start outside the grid reaches nothing.
+pub fn bfs<T, P>(grid: &Grid<T>, start: (usize, usize), passable: P) -> Search<usize>
+where
+    P: Fn(&T) -> bool,
+{
+    let mut search = Search::new(grid.width, grid.height, 1);
+    let start = State::new(start, 0);
+    if !search.contains(start) {
+        return search;
+    }
+    let start = search.id(start);
+    search.distances[start] = Some(0);
+    let mut queue = VecDeque::from([start]);
+
+    while let Some(id) = queue.pop_front() {
+        let (xy, distance) = (search.state(id).xy, search.distances[id]);
+        for next in Direction::cardinal().filter_map(|d| grid.step(xy, d)) {
+            let next_id = search.id(State::new(next, 0));
+            if search.distances[next_id].is_none() && passable(&grid[next]) {
+                search.distances[next_id] = distance.map(|d| d + 1);
+                search.predecessors[next_id] = Some(id);
+                queue.push_back(next_id);
+            }
+        }
+    }
+    search
+}
+
+/// Dijkstra's algorithm from `start`, moving orthogonally onto cells that are `passable` at a
+/// price of `step_cost(from, to)`.
+pub fn dijkstra<T, W, P, C>(
+    grid: &Grid<T>,
+    start: (usize, usize),
+    passable: P,
+    step_cost: C,
+) -> Search<W>
+where
+    W: Weight,
+    P: Fn(&T) -> bool,
+    C: Fn((usize, usize), (usize, usize)) -> W,
+{
+    dijkstra_states(
+        grid.width,
+        grid.height,
+        1,
+        [State::new(start, 0)],
+        cardinal_moves(grid, passable, step_cost),
+    )
+}
+
+/// A* from `start` to `goal`, stopping once `goal` is settled. `heuristic` must never
+/// overestimate the remaining cost, e.g. Manhattan distance times the cheapest step. States
+/// are reopened when a shorter route reaches them, so it need not also be consistent.
+pub fn astar<T, W, P, C, H>(
+    grid: &Grid<T>,
+    start: (usize, usize),
+    goal: (usize, usize),
+    passable: P,
+    step_cost: C,
+    heuristic: H,
+) -> Search<W>
+where
+    W: Weight,
+    P: Fn(&T) -> bool,
+    C: Fn((usize, usize), (usize, usize)) -> W,
+    H: Fn((usize, usize)) -> W,
+{
+    astar_states(
+        grid.width,
+        grid.height,
+        1,
+        [State::new(start, 0)],
+        cardinal_moves(grid, passable, step_cost),
+        |state| state.xy == goal,
+        |state| heuristic(state.xy),
+    )
+}
+
+fn cardinal_moves<'a, T, W, P, C>(
+    grid: &'a Grid<T>,
+    passable: P,
+    step_cost: C,
+) -> impl FnMut(State) -> Vec<(State, W)> + 'a
+where
+    P: Fn(&T) -> bool + 'a,
+    C: Fn((usize, usize), (usize, usize)) -> W + 'a,
+{
+    move |state| {
+        Direction::cardinal()
+            .filter_map(|d| grid.step(state.xy, d))
+            .filter(|&next| passable(&grid[next]))
+            .map(|next| (State::new(next, 0), step_cost(state.xy, next)))
+            .collect()
+    }
+}
+
+/// Dijkstra's algorithm over `layers` copies of a `width` x `height` grid, starting from every
+/// state in `starts`, ignoring any outside the grid and layers. `successors` lists the states
+/// reachable from a state and their costs; every state it returns must lie within them, and
+/// no cost may be negative.
+pub fn dijkstra_states<W, S, I>(
+    width: usize,
+    height: usize,
+    layers: usize,
+    starts: impl IntoIterator<Item = State>,
+    successors: S,
+) -> Search<W>
+where
+    W: Weight,
+    S: FnMut(State) -> I,
+    I: IntoIterator<Item = (State, W)>,
+{
+    astar_states(
+        width,
+        height,
+        layers,
+        starts,
+        successors,
+        |_| false,
+        |_| W::zero(),
+    )
+}
+
+/// Like [`dijkstra_states`], but guided by `heuristic` and stopping at the first state for
+/// which `is_goal` holds. That state is available from [`Search::goal`].
+pub fn astar_states<W, S, I, G, H>(
+    width: usize,
+    height: usize,
+    layers: usize,
+    starts: impl IntoIterator<Item = State>,
+    mut successors: S,
+    is_goal: G,
+    heuristic: H,
+) -> Search<W>
+where
+    W: Weight,
+    S: FnMut(State) -> I,
+    I: IntoIterator<Item = (State, W)>,
+    G: Fn(State) -> bool,
+    H: Fn(State) -> W,
+{
+    let mut search = Search::new(width, height, layers);
+    let mut queue = BinaryHeap::new();
+
+    for start in starts {
+        if !search.contains(start) {
+            continue;
+        }
+        let id = search.id(start);
+        search.distances[id] = Some(W::zero());
+        queue.push(Entry {
+            priority: heuristic(start),
+            distance: W::zero(),
+            id,
+        });
+    }
+
+    while let Some(Entry { id, distance, .. }) = queue.pop() {
+        // Skip entries superseded by a shorter route found since they were queued
+        if search.distances[id].is_some_and(|best| distance > best) {
+            continue;
+        }
+
+        let state = search.state(id);
+        if is_goal(state) {
+            search.goal = Some(id);
+            break;
+        }
+
+        for (next, cost) in successors(state) {
+            let next_id = search.id(next);
+            let next_distance = distance + non_negative(cost);
+            if search.distances[next_id].is_none_or(|d| next_distance < d) {
+                search.distances[next_id] = Some(next_distance);
+                search.predecessors[next_id] = Some(id);
+                queue.push(Entry {
+                    priority: next_distance + heuristic(next),
+                    distance: next_distance,
+                    id: next_id,
+                });
+            }
+        }
+    }
+    search
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn maze(rows: &[&str]) -> Grid<char> {
+        Grid::parse_str(&rows.join("\n"), Ok, '#').unwrap()
+    }
+
+    #[test]
+    fn test_bfs() {
+        let grid = maze(&["S.#", "#..", "..E"]);
+        let search = bfs(&grid, (0, 0), |&c| c != '#');
+
+        assert_eq!(search.distance(State::new((2, 2), 0)), Some(4));
+        assert_eq!(search.distance(State::new((2, 0), 0)), None);
+        assert_eq!(search.distances[2 * 3], Some(4));
+        assert_eq!(search.path_ids(8), Some(vec![0, 1, 4, 5, 8]));
+
+        let distances = search.distance_grid();
+        assert_eq!(distances[(0, 2)], Some(4));
+        assert_eq!(distances[(0, 1)], None);
+    }
+
+    #[test]
+    fn test_start_outside_grid() {
+        let grid = maze(&["..", ".."]);
+        let search = bfs(&grid, (2, 0), |_| true);
+        assert!(search.distances.iter().all(Option::is_none));
+
+        let search = dijkstra(&grid, (0, 5), |_| true, |_, _| 1u32);
+        assert!(search.distances.iter().all(Option::is_none));
+        let search: Search<u32> = dijkstra_states(2, 2, 1, [State::new((0, 0), 1)], |_| []);
+        assert!(search.distances.iter().all(Option::is_none));
+
+        let search = bfs(&grid, (0, 0), |_| true);
+        assert_eq!(search.distance(State::new((1, 1), 0)), Some(2));
+        assert_eq!(search.distance(State::new((2, 1), 0)), None);
+        assert_eq!(search.distance(State::new((1, 1), 1)), None);
+        assert_eq!(search.path(State::new((0, 2), 0)), None);
+    }
+
+    #[test]
+    fn test_dijkstra_and_astar() {
+        // Entering a cell costs its digit
+        let grid = Grid::parse_str(
+            &["1163", "1381", "2136", "3694"].join("\n"),
+            |c| c.to_digit(10).ok_or(format!("{c}")),
+            0,
+        )
+        .unwrap();
+        let cost = |_, to| grid[to];
+
+        let search = dijkstra(&grid, (0, 0), |_| true, cost);
+        assert_eq!(search.distance(State::new((3, 3), 0)), Some(17));
+        let path = search.path(State::new((3, 3), 0)).unwrap();
+        let total: u32 = path.iter().skip(1).map(|state| grid[state.xy]).sum();
+        assert_eq!(total, 17);
+
+        let goal = (3, 3);
+        let manhattan = |(x, y): (usize, usize)| (goal.0 - x + goal.1 - y) as u32;
+        let search = astar(&grid, (0, 0), goal, |_| true, cost, manhattan);
+        assert_eq!(search.goal(), Some(State::new(goal, 0)));
+        assert_eq!(search.distance(State::new(goal, 0)), Some(17));
+    }
+
+    #[test]
+    fn test_states_with_facing() {
+        // Moving forward costs 1 and turning 90 degrees costs 1000, starting east
+        let grid = maze(&[
+            "###############",
+            "#.......#....E#",
+            "#.#.###.#.###.#",
+            "#.....#.#...#.#",
+            "#.###.#####.#.#",
+            "#.#.#.......#.#",
+            "#.#.#####.###.#",
+            "#...........#.#",
+            "###.#.#####.#.#",
+            "#...#.....#.#.#",
+            "#.#.#.###.#.#.#",
+            "#.....#...#.#.#",
+            "#.###.#.#.#.#.#",
+            "#S..#.....#...#",
+            "###############",
+        ]);
+        let facing = |layer: usize| Direction::CARDINAL[layer];
+        let layer = |d: Direction| Direction::CARDINAL.iter().position(|&c| c == d).unwrap();
+
+        let successors = |state: State| {
+            let direction = facing(state.layer);
+            let mut next = vec![
+                (State::new(state.xy, layer(direction.turn_left())), 1000),
+                (State::new(state.xy, layer(direction.turn_right())), 1000),
+            ];
+            if let Some(xy) = grid.step(state.xy, direction).filter(|&xy| grid[xy] != '#') {
+                next.push((State::new(xy, state.layer), 1));
+            }
+            next
+        };
+        let start = State::new((1, 13), layer(Direction::Right));
+
+        let search = dijkstra_states(15, 15, 4, [start], successors);
+        let best = search.distance_grid()[(13, 1)];
+        assert_eq!(best, Some(7036));
+
+        let search = astar_states(
+            15,
+            15,
+            4,
+            [start],
+            successors,
+            |state| state.xy == (13, 1),
+            |_| 0usize,
+        );
+        let goal = search.goal().unwrap();
+        assert_eq!(search.distance(goal), Some(7036));
+        assert_eq!(search.path(goal).unwrap().first(), Some(&start));
+    }
+
+    #[test]
+    fn test_astar_reopens_states() {
+        // S A B G in a row. The heuristic at A is admissible but not consistent, so B is first
+        // settled at 3 straight from S before the cheaper route through A is found
+        let (s, a, b, g) = ((0, 0), (1, 0), (2, 0), (3, 0));
+        let successors = |state: State| {
+            let edges: &[((usize, usize), usize)] = match state.xy {
+                xy if xy == s => &[(a, 1), (b, 3)],
+                xy if xy == a => &[(b, 1)],
+                xy if xy == b => &[(g, 3)],
+                _ => &[],
+            };
+            edges
+                .iter()
+                .map(|&(xy, cost)| (State::new(xy, 0), cost))
+                .collect::<Vec<_>>()
+        };
+
+        let search = astar_states(
+            4,
+            1,
+            1,
+            [State::new(s, 0)],
+            successors,
+            |state| state.xy == g,
+            |state| if state.xy == a { 4 } else { 0 },
+        );
+        let goal = search.goal().unwrap();
+        assert_eq!(search.distance(goal), Some(5));
+        let path: Vec<_> = search.path(goal).unwrap().iter().map(|s| s.xy).collect();
+        assert_eq!(path, vec![s, a, b, g]);
+    }
+
+    #[test]
+    #[cfg(debug_assertions)]
+    #[should_panic(expected = "Negative edge weight")]
+    fn test_dijkstra_rejects_negative_weights() {
+        let grid = maze(&["..."]);
+        dijkstra(&grid, (0, 0), |_| true, |_, _| -1);
+    }
+}
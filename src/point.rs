@@ -0,0 +1,218 @@
+use num_traits::ToPrimitive;
+use std::num::TryFromIntError;
+use std::ops::{Add, AddAssign, Mul, Neg, Sub, SubAssign};
+
+/// A 2D point or vector. `y` grows downwards, matching [`Grid`](crate::Grid) rows.
+#[derive(Clone, Copy, Debug, Default, Hash, PartialEq, Eq, PartialOrd, Ord)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Point { x, y }
+    }
+}
+
+fn abs_diff<T: PartialOrd + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+impl<T> Point<T>
+where
+    T: Copy + PartialOrd + Add<Output = T> + Sub<Output = T>,
+{
+    /// Taxicab distance: the number of orthogonal steps between the points.
+    pub fn manhattan(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// Chessboard distance: the number of king moves between the points.
+    pub fn chebyshev(self, other: Self) -> T {
+        let dx = abs_diff(self.x, other.x);
+        let dy = abs_diff(self.y, other.y);
+        if dx > dy {
+            dx
+        } else {
+            dy
+        }
+    }
+
+    pub fn euclidean(self, other: Self) -> f64
+    where
+        T: ToPrimitive,
+    {
+        let dx = abs_diff(self.x, other.x).to_f64().unwrap_or(f64::NAN);
+        let dy = abs_diff(self.y, other.y).to_f64().unwrap_or(f64::NAN);
+        dx.hypot(dy)
+    }
+}
+
+impl<T: Neg<Output = T>> Point<T> {
+    /// Rotates 90° clockwise on screen, e.g. up `(0, -1)` becomes right `(1, 0)`.
+    pub fn rotate_cw(self) -> Self {
+        Point::new(-self.y, self.x)
+    }
+
+    /// Rotates 90° counter-clockwise on screen, e.g. right `(1, 0)` becomes up `(0, -1)`.
+    pub fn rotate_ccw(self) -> Self {
+        Point::new(self.y, -self.x)
+    }
+}
+
+impl Point<usize> {
+    /// Offsets an index-like point, or `None` if either coordinate would become negative.
+    pub fn checked_add_signed(self, offset: Point<isize>) -> Option<Self> {
+        Some(Point::new(
+            self.x.checked_add_signed(offset.x)?,
+            self.y.checked_add_signed(offset.y)?,
+        ))
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self {
+        Point::new(self.x + other.x, self.y + other.y)
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self {
+        Point::new(self.x - other.x, self.y - other.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, other: Self) {
+        self.x += other.x;
+        self.y += other.y;
+    }
+}
+
+impl<T: SubAssign> SubAssign for Point<T> {
+    fn sub_assign(&mut self, other: Self) {
+        self.x -= other.x;
+        self.y -= other.y;
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self {
+        Point::new(-self.x, -self.y)
+    }
+}
+
+impl<T: Copy + Mul<Output = T>> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, k: T) -> Self {
+        Point::new(self.x * k, self.y * k)
+    }
+}
+
+impl<T> From<(T, T)> for Point<T> {
+    fn from((x, y): (T, T)) -> Self {
+        Point::new(x, y)
+    }
+}
+
+impl<T> From<Point<T>> for (T, T) {
+    fn from(point: Point<T>) -> Self {
+        (point.x, point.y)
+    }
+}
+
+macro_rules! signed_conversions {
+    ($($t:ty),*) => {$(
+        impl TryFrom<(usize, usize)> for Point<$t> {
+            type Error = TryFromIntError;
+
+            fn try_from((x, y): (usize, usize)) -> Result<Self, Self::Error> {
+                Ok(Point::new(x.try_into()?, y.try_into()?))
+            }
+        }
+
+        /// Fails if either coordinate is negative.
+        impl TryFrom<Point<$t>> for (usize, usize) {
+            type Error = TryFromIntError;
+
+            fn try_from(point: Point<$t>) -> Result<Self, Self::Error> {
+                Ok((point.x.try_into()?, point.y.try_into()?))
+            }
+        }
+    )*};
+}
+
+signed_conversions!(i32, i64, isize);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_arithmetic() {
+        let a = Point::new(3, -2);
+        let b = Point::new(-1, 5);
+        assert_eq!(a + b, Point::new(2, 3));
+        assert_eq!(a - b, Point::new(4, -7));
+        assert_eq!(-a, Point::new(-3, 2));
+        assert_eq!(a * 3, Point::new(9, -6));
+
+        let mut c = a;
+        c += b;
+        c -= a;
+        assert_eq!(c, b);
+    }
+
+    #[test]
+    fn test_distances() {
+        let a = Point::new(1, 1);
+        let b = Point::new(4, 5);
+        assert_eq!(a.manhattan(b), 7);
+        assert_eq!(a.chebyshev(b), 4);
+        assert_eq!(a.euclidean(b), 5.0);
+
+        let (c, d) = (Point::new(2usize, 9), Point::new(7usize, 3));
+        assert_eq!(c.manhattan(d), 11);
+        assert_eq!(d.manhattan(c), 11);
+    }
+
+    #[test]
+    fn test_rotation() {
+        let up = Point::new(0, -1);
+        let right = up.rotate_cw();
+        assert_eq!(right, Point::new(1, 0));
+        assert_eq!(right.rotate_cw(), Point::new(0, 1));
+        assert_eq!(right.rotate_ccw(), up);
+        assert_eq!(Point::new(2, 3).rotate_cw().rotate_cw(), Point::new(-2, -3));
+    }
+
+    #[test]
+    fn test_conversions() {
+        assert_eq!(
+            Point::<i32>::try_from((4usize, 7usize)),
+            Ok(Point::new(4, 7))
+        );
+        assert_eq!(<(usize, usize)>::try_from(Point::new(4i64, 7)), Ok((4, 7)));
+        assert!(<(usize, usize)>::try_from(Point::new(-1i32, 7)).is_err());
+        assert_eq!(<(u8, u8)>::from(Point::new(1, 2)), (1, 2));
+
+        let origin = Point::new(0usize, 3);
+        assert_eq!(
+            origin.checked_add_signed(Point::new(2, -3)),
+            Some(Point::new(2, 0))
+        );
+        assert_eq!(origin.checked_add_signed(Point::new(-1, 0)), None);
+    }
+}
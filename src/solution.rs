@@ -0,0 +1,63 @@
+use crate::AocError;
+use std::fmt::Display;
+
+/// A solver for a single day's puzzle.
+///
+/// The raw input is parsed once into `Input`, which both parts then share.
+pub trait Solution {
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input, AocError>;
+    fn part1(input: &Self::Input) -> Result<Self::Answer1, AocError>;
+    fn part2(input: &Self::Input) -> Result<Self::Answer2, AocError>;
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Part {
+    One,
+    Two,
+}
+
+/// Rendered answers for the parts that were run.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Answers {
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+type Solver = fn(&str, Option<Part>) -> Result<Answers, AocError>;
+
+/// A type-erased `Solution`, so days with different `Input` types can share a registry.
+pub struct Day {
+    pub day: u8,
+    solve: Solver,
+}
+
+impl Day {
+    pub const fn new<S: Solution>(day: u8) -> Self {
+        Day {
+            day,
+            solve: solve::<S>,
+        }
+    }
+
+    /// Parses `input` once and runs `part`, or both parts if `None`.
+    pub fn solve(&self, input: &str, part: Option<Part>) -> Result<Answers, AocError> {
+        (self.solve)(input, part)
+    }
+}
+
+fn solve<S: Solution>(input: &str, part: Option<Part>) -> Result<Answers, AocError> {
+    let input = S::parse(input)?;
+    let mut answers = Answers::default();
+
+    if part != Some(Part::Two) {
+        answers.part1 = Some(S::part1(&input)?.to_string());
+    }
+    if part != Some(Part::One) {
+        answers.part2 = Some(S::part2(&input)?.to_string());
+    }
+    Ok(answers)
+}
@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+
+/// Disjoint sets of `N`, merged with [`union`](UnionFind::union) in near-constant time.
+///
+/// Elements are added on first use. Each set is identified by the index of its current
+/// representative, which can change as sets are merged.
+#[derive(Clone, Debug)]
+pub struct UnionFind<N> {
+    elements: Vec<N>,
+    indices: HashMap<N, usize>,
+    parents: Vec<usize>,
+    sizes: Vec<usize>,
+    set_count: usize,
+}
+
+impl<N> Default for UnionFind<N> {
+    fn default() -> Self {
+        UnionFind {
+            elements: Vec::new(),
+            indices: HashMap::new(),
+            parents: Vec::new(),
+            sizes: Vec::new(),
+            set_count: 0,
+        }
+    }
+}
+
+impl<N: Eq + Hash + Clone> UnionFind<N> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `element` as a set of its own if it is new, and returns its index.
+    pub fn insert(&mut self, element: N) -> usize {
+        if let Some(&index) = self.indices.get(&element) {
+            return index;
+        }
+        let index = self.elements.len();
+        self.elements.push(element.clone());
+        self.indices.insert(element, index);
+        self.parents.push(index);
+        self.sizes.push(1);
+        self.set_count += 1;
+        index
+    }
+
+    /// The number of elements across all sets.
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    pub fn set_count(&self) -> usize {
+        self.set_count
+    }
+
+    fn root(&mut self, mut index: usize) -> usize {
+        // Path halving: point every other node on the way up at its grandparent
+        while self.parents[index] != index {
+            self.parents[index] = self.parents[self.parents[index]];
+            index = self.parents[index];
+        }
+        index
+    }
+
+    /// The representative of the set containing `element`, or `None` if it was never added.
+    pub fn find(&mut self, element: &N) -> Option<usize> {
+        let index = *self.indices.get(element)?;
+        Some(self.root(index))
+    }
+
+    /// Merges the sets containing `a` and `b`, adding either if new. Returns `false` if they
+    /// were already in the same set.
+    pub fn union(&mut self, a: N, b: N) -> bool {
+        let a = self.insert(a);
+        let b = self.insert(b);
+        let (mut a, mut b) = (self.root(a), self.root(b));
+        if a == b {
+            return false;
+        }
+
+        // Hang the smaller tree under the larger to keep paths short
+        if self.sizes[a] < self.sizes[b] {
+            (a, b) = (b, a);
+        }
+        self.parents[b] = a;
+        self.sizes[a] += self.sizes[b];
+        self.set_count -= 1;
+        true
+    }
+
+    pub fn same_set(&mut self, a: &N, b: &N) -> bool {
+        match (self.find(a), self.find(b)) {
+            (Some(a), Some(b)) => a == b,
+            _ => false,
+        }
+    }
+
+    /// The number of elements in the set containing `element`.
+    pub fn set_size(&mut self, element: &N) -> Option<usize> {
+        let root = self.find(element)?;
+        Some(self.sizes[root])
+    }
+
+    /// Numbers the sets `0..set_count()` in the order their first element was added, and maps
+    /// each element to its set's number.
+    pub fn labels(&mut self) -> HashMap<N, usize> {
+        let mut numbers: HashMap<usize, usize> = HashMap::new();
+        let mut labels = HashMap::with_capacity(self.elements.len());
+        for index in 0..self.elements.len() {
+            let root = self.root(index);
+            let next = numbers.len();
+            let label = *numbers.entry(root).or_insert(next);
+            labels.insert(self.elements[index].clone(), label);
+        }
+        labels
+    }
+
+    /// The elements of each set, numbered as in [`labels`](Self::labels) and each in the order
+    /// its elements were added.
+    pub fn sets(&mut self) -> Vec<Vec<N>> {
+        let mut numbers: HashMap<usize, usize> = HashMap::new();
+        let mut sets: Vec<Vec<N>> = Vec::new();
+        for index in 0..self.elements.len() {
+            let root = self.root(index);
+            let label = *numbers.entry(root).or_insert_with(|| {
+                sets.push(Vec::new());
+                sets.len() - 1
+            });
+            sets[label].push(self.elements[index].clone());
+        }
+        sets
+    }
+}
+
+impl<N: Eq + Hash + Clone> FromIterator<N> for UnionFind<N> {
+    /// Every element in a set of its own.
+    fn from_iter<I: IntoIterator<Item = N>>(iter: I) -> Self {
+        let mut union_find = UnionFind::new();
+        for element in iter {
+            union_find.insert(element);
+        }
+        union_find
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_union_find() {
+        let mut sets: UnionFind<char> = "abcdef".chars().collect();
+        assert_eq!((sets.len(), sets.set_count()), (6, 6));
+
+        assert!(sets.union('a', 'b'));
+        assert!(sets.union('c', 'd'));
+        assert!(sets.union('b', 'd'));
+        assert!(!sets.union('a', 'c'));
+        assert!(sets.union('g', 'f'));
+
+        assert_eq!((sets.len(), sets.set_count()), (7, 3));
+        assert!(sets.same_set(&'a', &'d'));
+        assert!(!sets.same_set(&'a', &'e'));
+        assert!(!sets.same_set(&'a', &'z'));
+        assert_eq!(sets.find(&'z'), None);
+        assert_eq!(sets.set_size(&'c'), Some(4));
+        assert_eq!(sets.set_size(&'g'), Some(2));
+    }
+
+    #[test]
+    fn test_labels_and_sets() {
+        let mut sets = UnionFind::new();
+        sets.union(3, 1);
+        sets.insert(2);
+        sets.union(4, 1);
+
+        let labels = sets.labels();
+        assert_eq!(labels[&3], 0);
+        assert_eq!(labels[&1], 0);
+        assert_eq!(labels[&4], 0);
+        assert_eq!(labels[&2], 1);
+        assert_eq!(sets.sets(), vec![vec![3, 1, 4], vec![2]]);
+    }
+}